@@ -70,7 +70,7 @@ fn html_iter(input: &str) -> u64 {
     let iter = Iter::new(input);
     for elem in iter {
         match elem {
-            Elem::FeedUrl(_) => {
+            Elem::FeedLink(_) => {
                 count += 1;
             }
             Elem::BaseUrl(_) => {}
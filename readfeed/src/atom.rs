@@ -92,6 +92,9 @@
 //!
 //! [atom]: https://datatracker.ietf.org/doc/html/rfc4287
 
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
 use maybe_xml::{
     token::{
         self,
@@ -101,7 +104,19 @@ use maybe_xml::{
     Reader,
 };
 
-use crate::{xml, Tag};
+#[cfg(feature = "html-sanitize")]
+use crate::sanitize::Policy;
+#[cfg(feature = "alloc")]
+use crate::text;
+use crate::{
+    date::DateTime,
+    ext::{dc, fh, media, thr},
+    lang::Language,
+    uri::Base,
+    value::{self, ParseError},
+    xml::{self, Scope},
+    Tag,
+};
 
 macro_rules! content_elem {
     ($name:ident $(,)?) => {
@@ -109,6 +124,7 @@ macro_rules! content_elem {
         pub struct $name<'a> {
             tag: Tag<'a>,
             content: &'a str,
+            scope: Scope<'a>,
         }
 
         impl<'a> $name<'a> {
@@ -123,6 +139,23 @@ macro_rules! content_elem {
             pub const fn attributes(&self) -> Option<Attributes<'a>> {
                 self.tag.attributes()
             }
+
+            /// The `xml:base` values which apply to the element.
+            #[inline]
+            #[must_use]
+            pub const fn base(&self) -> Base<'a> {
+                self.scope.base()
+            }
+
+            /// The language of the element.
+            ///
+            /// See the [`lang`][crate::lang] module for how the language is
+            /// determined.
+            #[inline]
+            #[must_use]
+            pub fn lang(&self) -> Option<Language<'a>> {
+                self.scope.lang()
+            }
         }
     };
     ($name:ident, $($nms:ident),+ $(,)?) => {
@@ -153,6 +186,36 @@ macro_rules! impl_text_construct {
             pub fn ty(&self) -> Option<AttributeValue<'a>> {
                 self.tag.find_attribute("type")
             }
+
+            /// The value with the escaping for the `type` applied.
+            ///
+            /// A missing or unknown `type` is treated as `text`.
+            #[cfg(feature = "alloc")]
+            #[must_use]
+            pub fn to_text_construct(&self) -> TextConstruct {
+                TextConstruct::new(self.ty().map(|ty| ty.as_str()), self.content)
+            }
+
+            /// The value as plain text.
+            ///
+            /// See [`TextConstruct::to_plain_text()`].
+            #[cfg(feature = "alloc")]
+            #[must_use]
+            pub fn to_plain_text(&self) -> String {
+                self.to_text_construct().to_plain_text()
+            }
+
+            /// The value as sanitized HTML.
+            ///
+            /// Relative URLs are resolved against the base URI of the
+            /// element.
+            #[cfg(feature = "html-sanitize")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+            #[must_use]
+            pub fn to_sanitized_html(&self, document_url: &str, policy: &Policy) -> String {
+                self.to_text_construct()
+                    .to_safe_html(Some(&self.scope.base().to_uri(document_url)), policy)
+            }
         }
     };
     ($name:ident, $($nms:ident),+ $(,)?) => {
@@ -161,9 +224,41 @@ macro_rules! impl_text_construct {
     };
 }
 
+macro_rules! impl_xhtml {
+    ($name:ident $(,)?) => {
+        impl<'a> $name<'a> {
+            /// The XHTML markup without the wrapping `div` element if the
+            /// `type` is `xhtml`.
+            ///
+            /// Namespace prefixes on the XHTML elements are removed (e.g.
+            /// `<xhtml:p>` becomes `<p>`).
+            #[cfg(feature = "alloc")]
+            #[must_use]
+            pub fn xhtml(&self) -> Option<Cow<'a, str>> {
+                self.ty()
+                    .filter(|ty| ty.as_str().trim().eq_ignore_ascii_case("xhtml"))
+                    .map(|_| xml::unwrap_xhtml(self.content))
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        impl_xhtml!($name);
+        impl_xhtml!($($nms),+);
+    };
+}
+
 macro_rules! impl_date_construct {
     ($name:ident $(,)?) => {
         content_elem!($name);
+
+        impl<'a> $name<'a> {
+            /// Parses the content as a W3C date and time.
+            #[inline]
+            #[must_use]
+            pub fn to_date_time(&self) -> Option<DateTime> {
+                DateTime::parse_w3cdtf(self.content)
+            }
+        }
     };
     ($name:ident, $($nms:ident),+ $(,)?) => {
         impl_date_construct!($name);
@@ -174,6 +269,17 @@ macro_rules! impl_date_construct {
 macro_rules! impl_uri_construct {
     ($name:ident $(,)?) => {
         content_elem!($name);
+
+        impl<'a> $name<'a> {
+            /// Resolves the content against the base URI of the element.
+            #[cfg(feature = "alloc")]
+            #[must_use]
+            pub fn resolve(&self, document_url: &str) -> String {
+                self.scope
+                    .base()
+                    .resolve(document_url, &xml::decode(self.content))
+            }
+        }
     };
     ($name:ident, $($nms:ident),+ $(,)?) => {
         impl_uri_construct!($name);
@@ -188,6 +294,7 @@ macro_rules! impl_iter {
             tag: Tag<'a>,
             reader: Reader<'a>,
             pos: usize,
+            scope: Scope<'a>,
         }
 
         impl_iter!($iter_name, $elem_ty, $fn_name);
@@ -198,6 +305,23 @@ macro_rules! impl_iter {
             pub const fn attributes(&self) -> Option<Attributes<'a>> {
                 self.tag.attributes()
             }
+
+            /// The `xml:base` values which apply to the element.
+            #[inline]
+            #[must_use]
+            pub const fn base(&self) -> Base<'a> {
+                self.scope.base()
+            }
+
+            /// The language of the element.
+            ///
+            /// See the [`lang`][crate::lang] module for how the language is
+            /// determined.
+            #[inline]
+            #[must_use]
+            pub fn lang(&self) -> Option<Language<'a>> {
+                self.scope.lang()
+            }
         }
     };
     ($iter_name:ident, $elem_ty:ident, $fn_name:expr) => {
@@ -216,12 +340,17 @@ macro_rules! impl_iter {
                                 &mut self.pos,
                             );
 
-                            return Some($fn_name(Tag::Start(tag), tag_name, content));
+                            return Some($fn_name(Tag::Start(tag), tag_name, content, self.scope));
                         }
                         token::Ty::EmptyElementTag(tag) => {
                             let tag_name = tag.name();
 
-                            return Some($fn_name(Tag::EmptyElement(tag), tag_name, ""));
+                            return Some($fn_name(
+                                Tag::EmptyElement(tag),
+                                tag_name,
+                                "",
+                                self.scope,
+                            ));
                         }
                         token::Ty::Characters(content) => {
                             if content.content().as_str().trim().is_empty() {
@@ -246,6 +375,101 @@ macro_rules! impl_iter {
     };
 }
 
+/// The value of a text construct.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TextConstruct {
+    /// Plain text with character references decoded.
+    Text(String),
+    /// HTML markup with the escaping for the document removed.
+    Html(String),
+    /// XHTML markup without the wrapping `div` element.
+    Xhtml(String),
+}
+
+#[cfg(feature = "alloc")]
+impl TextConstruct {
+    #[must_use]
+    fn new(ty: Option<&str>, content: &str) -> Self {
+        match ty.map(str::trim) {
+            Some(ty) if ty.eq_ignore_ascii_case("html") => {
+                TextConstruct::Html(xml::decode(content).into_owned())
+            }
+            Some(ty) if ty.eq_ignore_ascii_case("xhtml") => {
+                TextConstruct::Xhtml(xml::unwrap_xhtml(content).into_owned())
+            }
+            _ => TextConstruct::Text(xml::decode(content).into_owned()),
+        }
+    }
+
+    /// The text or markup.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            TextConstruct::Text(value)
+            | TextConstruct::Html(value)
+            | TextConstruct::Xhtml(value) => value,
+        }
+    }
+
+    /// Converts the value to plain text.
+    ///
+    /// Text is trimmed. Markup is converted with [`text::html_to_text()`] which
+    /// keeps paragraph breaks and list items.
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        match self {
+            TextConstruct::Text(value) => String::from(value.trim()),
+            TextConstruct::Html(value) | TextConstruct::Xhtml(value) => text::html_to_text(value),
+        }
+    }
+
+    /// Converts the value to HTML without sanitizing it.
+    ///
+    /// Text is escaped. Markup is returned as is, so the output must not be
+    /// embedded in a document without sanitizing it first. See
+    /// [`TextConstruct::to_safe_html()`].
+    #[must_use]
+    pub fn to_unsanitized_html(&self) -> String {
+        match self {
+            TextConstruct::Text(value) => text::escape_html(value),
+            TextConstruct::Html(value) | TextConstruct::Xhtml(value) => value.clone(),
+        }
+    }
+
+    /// Converts the value to sanitized HTML.
+    ///
+    /// Text is escaped. Markup is sanitized with the policy. Relative URLs are
+    /// resolved against the base URL if one is given.
+    #[cfg(feature = "html-sanitize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+    #[must_use]
+    pub fn to_safe_html(&self, base_url: Option<&str>, policy: &Policy) -> String {
+        policy.sanitize(&self.to_unsanitized_html(), base_url)
+    }
+}
+
+/// The classified value of a content element.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContentValue<'a> {
+    /// Inline content of the `text`, `html`, or `xhtml` type.
+    Text(TextConstruct),
+    /// Inline text of a `text/*` media type.
+    MediaText { media_type: &'a str, text: String },
+    /// Inline XML of an XML media type.
+    Xml { media_type: &'a str, xml: &'a str },
+    /// Inline base64 decoded data of any other media type.
+    Binary { media_type: &'a str, data: Vec<u8> },
+    /// Content which is referenced by the `src` attribute.
+    OutOfLine {
+        media_type: Option<&'a str>,
+        src: &'a str,
+    },
+}
+
 content_elem!(Unknown);
 
 impl<'a> Unknown<'a> {
@@ -264,6 +488,53 @@ impl_attr!(Link, hreflang, "hreflang");
 impl_attr!(Link, title, "title");
 impl_attr!(Link, length, "length");
 
+impl<'a> Link<'a> {
+    /// The `thr:count` attribute parsed as the number of replies.
+    #[must_use]
+    pub fn thr_count(&self) -> Option<u64> {
+        thr::find_attribute(self.tag.attributes(), "count")?
+            .as_str()
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// The `thr:updated` attribute, the date of the most recent reply.
+    #[must_use]
+    pub fn thr_updated(&self) -> Option<AttributeValue<'a>> {
+        thr::find_attribute(self.tag.attributes(), "updated")
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(tag: Tag<'a>, content: &'a str, scope: Scope<'a>) -> Self {
+        Self {
+            tag,
+            content,
+            scope,
+        }
+    }
+
+    /// Resolves the `href` attribute against the base URI of the element.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve_href(&self, document_url: &str) -> Option<String> {
+        let href = self.href()?;
+        Some(
+            self.scope
+                .base()
+                .resolve(document_url, &xml::decode(href.as_str())),
+        )
+    }
+
+    /// Parses the `length` attribute as a size in bytes.
+    #[inline]
+    #[must_use]
+    pub fn length_value(&self) -> Option<Result<u64, ParseError>> {
+        self.length().map(|len| value::parse_u64(len.as_str()))
+    }
+}
+
 content_elem!(Category);
 impl_attr!(Category, term, "term");
 impl_attr!(Category, scheme, "scheme");
@@ -273,6 +544,123 @@ content_elem!(Content);
 impl_attr!(Content, ty, "type");
 impl_attr!(Content, src, "src");
 
+impl<'a> Content<'a> {
+    /// Classifies the content following the processing model of
+    /// [RFC 4287][rfc4287].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content of a binary media type is not valid
+    /// base64.
+    ///
+    /// [rfc4287]: https://www.rfc-editor.org/rfc/rfc4287#section-4.1.3.3
+    #[cfg(feature = "alloc")]
+    pub fn to_content_value(&self) -> Result<ContentValue<'a>, ParseError> {
+        let ty = self.ty().map(|ty| ty.as_str().trim());
+
+        if let Some(src) = self.src() {
+            return Ok(ContentValue::OutOfLine {
+                media_type: ty,
+                src: src.as_str().trim(),
+            });
+        }
+
+        let Some(media_type) = ty.filter(|ty| ty.contains('/')) else {
+            return Ok(ContentValue::Text(TextConstruct::new(ty, self.content)));
+        };
+        // Ignore any parameters (e.g. `; charset=utf-8`)
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        let is_xml = essence
+            .get(essence.len().saturating_sub(4)..)
+            .is_some_and(|end| {
+                end.eq_ignore_ascii_case("+xml") || end.eq_ignore_ascii_case("/xml")
+            });
+        let is_text = essence
+            .get(..5)
+            .is_some_and(|start| start.eq_ignore_ascii_case("text/"));
+
+        Ok(if is_xml {
+            ContentValue::Xml {
+                media_type,
+                xml: self.content.trim(),
+            }
+        } else if is_text {
+            ContentValue::MediaText {
+                media_type,
+                text: xml::decode(self.content).into_owned(),
+            }
+        } else {
+            ContentValue::Binary {
+                media_type,
+                data: value::decode_base64(&xml::decode(self.content))?,
+            }
+        })
+    }
+
+    /// The inline content as plain text.
+    ///
+    /// Markup is converted with [`text::html_to_text()`]. Returns `None` if the
+    /// content is out-of-line, XML, or binary data.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_plain_text(&self) -> Option<String> {
+        match self.to_content_value().ok()? {
+            ContentValue::Text(value) => Some(value.to_plain_text()),
+            ContentValue::MediaText { media_type, text } => {
+                let essence = media_type.split(';').next().unwrap_or_default().trim();
+                Some(if essence.eq_ignore_ascii_case("text/html") {
+                    text::html_to_text(&text)
+                } else {
+                    text
+                })
+            }
+            ContentValue::Xml { .. }
+            | ContentValue::Binary { .. }
+            | ContentValue::OutOfLine { .. } => None,
+        }
+    }
+
+    /// The inline content as sanitized HTML.
+    ///
+    /// Text is escaped. Relative URLs are resolved against the base URI of the
+    /// element. Returns `None` if the content is out-of-line, XML, or binary
+    /// data.
+    #[cfg(feature = "html-sanitize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+    #[must_use]
+    pub fn to_sanitized_html(&self, document_url: &str, policy: &Policy) -> Option<String> {
+        let html = match self.to_content_value().ok()? {
+            ContentValue::Text(value) => value.to_unsanitized_html(),
+            ContentValue::MediaText { media_type, text } => {
+                let essence = media_type.split(';').next().unwrap_or_default().trim();
+                if essence.eq_ignore_ascii_case("text/html") {
+                    text
+                } else {
+                    text::escape_html(&text)
+                }
+            }
+            ContentValue::Xml { .. }
+            | ContentValue::Binary { .. }
+            | ContentValue::OutOfLine { .. } => {
+                return None;
+            }
+        };
+        Some(policy.sanitize(&html, Some(&self.scope.base().to_uri(document_url))))
+    }
+
+    /// Resolves the `src` attribute against the base URI of the element.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve_src(&self, document_url: &str) -> Option<String> {
+        let src = self.src()?;
+        Some(
+            self.scope
+                .base()
+                .resolve(document_url, &xml::decode(src.as_str())),
+        )
+    }
+}
+
 impl_text_construct!(Generator);
 impl_attr!(Generator, uri, "uri");
 impl_attr!(Generator, version, "version");
@@ -283,6 +671,8 @@ impl_date_construct!(Published, Updated);
 
 impl_text_construct!(Rights, Subtitle, Summary, Title);
 
+impl_xhtml!(Content, Rights, Subtitle, Summary, Title);
+
 content_elem!(PersonName, PersonEmail);
 
 impl_uri_construct!(PersonUri);
@@ -328,6 +718,9 @@ pub enum EntryElem<'a> {
     Summary(Summary<'a>),
     Title(Title<'a>),
     Updated(Updated<'a>),
+    Dc(dc::Elem<'a>),
+    Media(media::Elem<'a>),
+    Thr(thr::Elem<'a>),
     Unknown(Unknown<'a>),
     Raw(Token<'a>),
 }
@@ -347,6 +740,8 @@ pub enum FeedElem<'a> {
     Title(Title<'a>),
     Updated(Updated<'a>),
     Entry(EntryIter<'a>),
+    Dc(dc::Elem<'a>),
+    Fh(fh::Elem<'a>),
     Unknown(Unknown<'a>),
     Raw(Token<'a>),
 }
@@ -358,14 +753,34 @@ pub enum Elem<'a> {
     Raw(Token<'a>),
 }
 
+/// Returns true if the tag name has a namespace prefix which is not the
+/// conventional `atom` prefix.
+#[must_use]
+fn is_foreign(tag_name: TagName<'_>) -> bool {
+    tag_name
+        .namespace_prefix()
+        .map(|prefix| !prefix.as_str().eq_ignore_ascii_case("atom"))
+        .unwrap_or_default()
+}
+
 impl<'a> PersonElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> PersonElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> PersonElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content_with_tag {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
@@ -374,18 +789,32 @@ impl<'a> PersonElem<'a> {
         return_content_with_tag!("uri", PersonUri, PersonElem::Uri);
         return_content_with_tag!("email", PersonEmail, PersonElem::Email);
 
-        PersonElem::Unknown(Unknown { tag, content })
+        PersonElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> SourceElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> SourceElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> SourceElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content_with_tag {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
@@ -397,11 +826,20 @@ impl<'a> SourceElem<'a> {
                         tag,
                         reader: Reader::from_str(content),
                         pos: 0,
+                        scope,
                     });
                 }
             };
         }
 
+        if is_foreign(tag_name) {
+            return SourceElem::Unknown(Unknown {
+                tag,
+                content,
+                scope,
+            });
+        }
+
         return_iter!("author", PersonIter, SourceElem::Author);
 
         return_content_with_tag!("category", Category, SourceElem::Category);
@@ -418,18 +856,32 @@ impl<'a> SourceElem<'a> {
         return_content_with_tag!("title", Title, SourceElem::Title);
         return_content_with_tag!("updated", Updated, SourceElem::Updated);
 
-        SourceElem::Unknown(Unknown { tag, content })
+        SourceElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> EntryElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> EntryElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> EntryElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content_with_tag {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
@@ -441,11 +893,32 @@ impl<'a> EntryElem<'a> {
                         tag,
                         reader: Reader::from_str(content),
                         pos: 0,
+                        scope,
                     });
                 }
             };
         }
 
+        if dc::is_dc(tag_name) {
+            return EntryElem::Dc(dc::Elem::new(tag, tag_name, content));
+        }
+
+        if media::is_media(tag_name) {
+            return EntryElem::Media(media::Elem::new(tag, tag_name, content));
+        }
+
+        if thr::is_thr(tag_name) {
+            return EntryElem::Thr(thr::Elem::new(tag, tag_name, content));
+        }
+
+        if is_foreign(tag_name) {
+            return EntryElem::Unknown(Unknown {
+                tag,
+                content,
+                scope,
+            });
+        }
+
         return_iter!("author", PersonIter, EntryElem::Author);
 
         return_content_with_tag!("category", Category, EntryElem::Category);
@@ -464,18 +937,32 @@ impl<'a> EntryElem<'a> {
         return_content_with_tag!("title", Title, EntryElem::Title);
         return_content_with_tag!("updated", Updated, EntryElem::Updated);
 
-        EntryElem::Unknown(Unknown { tag, content })
+        EntryElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> FeedElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> FeedElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> FeedElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content_with_tag {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
@@ -487,11 +974,28 @@ impl<'a> FeedElem<'a> {
                         tag,
                         reader: Reader::from_str(content),
                         pos: 0,
+                        scope,
                     });
                 }
             };
         }
 
+        if dc::is_dc(tag_name) {
+            return FeedElem::Dc(dc::Elem::new(tag, tag_name, content));
+        }
+
+        if fh::is_fh(tag_name) {
+            return FeedElem::Fh(fh::Elem::new(tag, tag_name, content));
+        }
+
+        if is_foreign(tag_name) {
+            return FeedElem::Unknown(Unknown {
+                tag,
+                content,
+                scope,
+            });
+        }
+
         return_iter!("entry", EntryIter, FeedElem::Entry);
 
         return_iter!("author", PersonIter, FeedElem::Author);
@@ -510,12 +1014,17 @@ impl<'a> FeedElem<'a> {
         return_content_with_tag!("title", Title, FeedElem::Title);
         return_content_with_tag!("updated", Updated, FeedElem::Updated);
 
-        FeedElem::Unknown(Unknown { tag, content })
+        FeedElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> Elem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str, scope: Scope<'a>) -> Elem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_iter {
@@ -525,6 +1034,7 @@ impl<'a> Elem<'a> {
                         tag,
                         reader: Reader::from_str(content),
                         pos: 0,
+                        scope,
                     });
                 }
             };
@@ -532,7 +1042,11 @@ impl<'a> Elem<'a> {
 
         return_iter!("feed", FeedIter, Elem::Feed);
 
-        Elem::Unknown(Unknown { tag, content })
+        Elem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
@@ -545,6 +1059,7 @@ impl_iter!(with_tag FeedIter, FeedElem, FeedElem::new);
 pub struct Iter<'a> {
     reader: Reader<'a>,
     pos: usize,
+    scope: Scope<'a>,
 }
 
 impl<'a> Iter<'a> {
@@ -554,8 +1069,20 @@ impl<'a> Iter<'a> {
         Self {
             reader: Reader::from_str(input),
             pos: 0,
+            scope: Scope::default(),
         }
     }
+
+    /// Sets the `Content-Language` of the document (e.g. from the HTTP
+    /// response).
+    ///
+    /// It is used as the language of elements which do not otherwise have a
+    /// language.
+    #[must_use]
+    pub fn with_content_language(mut self, language: &'a str) -> Self {
+        self.scope = self.scope.with_content_language(language);
+        self
+    }
 }
 
 impl_iter!(Iter, Elem, Elem::new);
@@ -564,6 +1091,9 @@ impl_iter!(Iter, Elem, Elem::new);
 mod tests {
     use super::*;
 
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+
     #[allow(clippy::too_many_lines)]
     #[test]
     fn eval_atom_1() {
@@ -663,4 +1193,278 @@ mod tests {
 
         assert_eq!(None, feed_iter.next());
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn eval_xml_base() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xml:base="https://example.com/blog/">
+    <icon>../icon.png</icon>
+    <entry xml:base="2024/">
+        <link href="first-post.html"/>
+        <content type="text/html" src="first-post.html#content"/>
+    </entry>
+    <entry xml:base="https://mirror.example.org/">
+        <link xml:base="a/" href="b?c=d&amp;e=f"/>
+    </entry>
+</feed>
+        "#;
+
+        let document_url = "https://example.com/feed.xml";
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+        if let Some(FeedElem::Icon(icon)) = feed_iter.next() {
+            assert_eq!("https://example.com/icon.png", icon.resolve(document_url));
+        } else {
+            panic!();
+        }
+
+        let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+        if let Some(EntryElem::Link(link)) = entry_iter.next() {
+            assert_eq!(
+                Some("https://example.com/blog/2024/first-post.html"),
+                link.resolve_href(document_url).as_deref()
+            );
+        } else {
+            panic!();
+        }
+        if let Some(EntryElem::Content(content)) = entry_iter.next() {
+            assert_eq!(
+                Some("https://example.com/blog/2024/first-post.html#content"),
+                content.resolve_src(document_url).as_deref()
+            );
+        } else {
+            panic!();
+        }
+        assert_eq!(None, entry_iter.next());
+
+        let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+        if let Some(EntryElem::Link(link)) = entry_iter.next() {
+            assert_eq!(2, link.base().iter().count());
+            assert_eq!(
+                Some("https://mirror.example.org/a/b?c=d&e=f"),
+                link.resolve_href(document_url).as_deref()
+            );
+        } else {
+            panic!();
+        }
+        assert_eq!(None, entry_iter.next());
+        assert_eq!(None, feed_iter.next());
+    }
+
+    #[test]
+    fn eval_xml_lang() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <title>Untitled</title>
+    <entry xml:lang="fr">
+        <title>Bonjour</title>
+        <content xml:lang="">?</content>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = Iter::new(input).with_content_language("en-GB");
+
+        let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+        if let Some(FeedElem::Title(title)) = feed_iter.next() {
+            assert_eq!(Some("en-GB"), title.lang().map(|lang| lang.as_str()));
+        } else {
+            panic!();
+        }
+
+        let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+        assert_eq!(Some("fr"), entry_iter.lang().map(|lang| lang.as_str()));
+        if let Some(EntryElem::Title(title)) = entry_iter.next() {
+            assert_eq!(Some("fr"), title.lang().map(|lang| lang.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(EntryElem::Content(content)) = entry_iter.next() {
+            assert_eq!(None, content.lang());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, entry_iter.next());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn eval_text_construct() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <title type="text">Lorem &lt;ipsum&gt;</title>
+    <subtitle type="xhtml">
+        <xhtml:div xmlns:xhtml="http://www.w3.org/1999/xhtml"><xhtml:em>Lorem</xhtml:em> ipsum</xhtml:div>
+    </subtitle>
+    <rights type="html">&amp;copy; 2024 &lt;b&gt;Jane&lt;/b&gt;</rights>
+</feed>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+        if let Some(FeedElem::Title(title)) = feed_iter.next() {
+            assert_eq!(None, title.xhtml());
+            let value = title.to_text_construct();
+            assert_eq!(TextConstruct::Text(String::from("Lorem <ipsum>")), value);
+            assert_eq!("Lorem <ipsum>", value.to_plain_text());
+            assert_eq!("Lorem &lt;ipsum&gt;", value.to_unsanitized_html());
+        } else {
+            panic!();
+        }
+        if let Some(FeedElem::Subtitle(subtitle)) = feed_iter.next() {
+            assert_eq!(Some("<em>Lorem</em> ipsum"), subtitle.xhtml().as_deref());
+            let value = subtitle.to_text_construct();
+            assert_eq!(
+                TextConstruct::Xhtml(String::from("<em>Lorem</em> ipsum")),
+                value
+            );
+            assert_eq!("Lorem ipsum", value.to_plain_text());
+        } else {
+            panic!();
+        }
+        if let Some(FeedElem::Rights(rights)) = feed_iter.next() {
+            let value = rights.to_text_construct();
+            assert_eq!(
+                TextConstruct::Html(String::from("&copy; 2024 <b>Jane</b>")),
+                value
+            );
+            assert_eq!("\u{a9} 2024 Jane", value.to_plain_text());
+            assert_eq!("&copy; 2024 <b>Jane</b>", value.to_unsanitized_html());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, feed_iter.next());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn eval_content_value() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <entry>
+        <content type="html">&lt;p&gt;Lorem&lt;/p&gt;</content>
+        <content type="image/png" src="/images/1.png"/>
+        <content type="image/png">iVBO
+            Rw==</content>
+        <content type="application/svg+xml"><svg xmlns="http://www.w3.org/2000/svg"/></content>
+        <content type="text/plain; charset=utf-8">1 &lt; 2</content>
+        <content type="application/octet-stream">not base64!</content>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(FeedElem::Entry(entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+
+        let values = entry_iter
+            .filter_map(|elem| match elem {
+                EntryElem::Content(content) => Some(content.to_content_value()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Ok(ContentValue::Text(TextConstruct::Html(String::from(
+                    "<p>Lorem</p>"
+                )))),
+                Ok(ContentValue::OutOfLine {
+                    media_type: Some("image/png"),
+                    src: "/images/1.png"
+                }),
+                Ok(ContentValue::Binary {
+                    media_type: "image/png",
+                    data: vec![0x89, b'P', b'N', b'G'],
+                }),
+                Ok(ContentValue::Xml {
+                    media_type: "application/svg+xml",
+                    xml: r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#,
+                }),
+                Ok(ContentValue::MediaText {
+                    media_type: "text/plain; charset=utf-8",
+                    text: String::from("1 < 2"),
+                }),
+            ],
+            values[..5]
+        );
+        assert_eq!(
+            Err(value::ErrorKind::InvalidBase64),
+            values[5].clone().map_err(|e| e.kind())
+        );
+    }
+
+    #[cfg(feature = "html-sanitize")]
+    #[test]
+    fn eval_sanitized_html() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xml:base="https://example.com/blog/">
+    <entry>
+        <summary>1 &lt; 2</summary>
+        <content type="html">&lt;p onclick="x()"&gt;&lt;img src="a.png"&gt;&lt;/p&gt;&lt;script&gt;x()&lt;/script&gt;</content>
+        <content type="image/png" src="/images/1.png"/>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+
+        let policy = Policy::new();
+        let url = "https://example.com/feed.xml";
+        let Some(EntryElem::Summary(summary)) = entry_iter.next() else {
+            panic!();
+        };
+        assert_eq!("1 &lt; 2", summary.to_sanitized_html(url, &policy));
+        let Some(EntryElem::Content(content)) = entry_iter.next() else {
+            panic!();
+        };
+        assert_eq!(
+            Some(String::from(
+                r#"<p><img src="https://example.com/blog/a.png"></p>"#
+            )),
+            content.to_sanitized_html(url, &policy)
+        );
+        let Some(EntryElem::Content(content)) = entry_iter.next() else {
+            panic!();
+        };
+        assert_eq!(None, content.to_sanitized_html(url, &policy));
+
+        let value = TextConstruct::Html(String::from(
+            r#"<a href="javascript:x()">Lorem</a> <a href="/ipsum">ipsum</a>"#,
+        ));
+        assert_eq!(
+            r#"<a>Lorem</a> <a href="https://example.com/ipsum">ipsum</a>"#,
+            value.to_safe_html(Some(url), &policy)
+        );
+        assert_eq!(
+            "1 &lt; 2",
+            TextConstruct::Text(String::from("1 < 2")).to_safe_html(None, &policy)
+        );
+    }
 }
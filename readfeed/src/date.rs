@@ -0,0 +1,579 @@
+//! Date and time values found in feeds.
+//!
+//! Dates in Atom documents and in Dublin Core elements use the [W3C Date and
+//! Time Formats][w3cdtf] profile of ISO 8601.
+//!
+//! Dates in RSS documents use the date and time specification of [RFC
+//! 822][rfc822], usually with the four digit years of [RFC 2822][rfc2822].
+//!
+//! [w3cdtf]: https://www.w3.org/TR/NOTE-datetime
+//! [rfc822]: https://www.rfc-editor.org/rfc/rfc822#section-5
+//! [rfc2822]: https://www.rfc-editor.org/rfc/rfc2822#section-3.3
+
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+/// A calendar date and time with an optional UTC offset.
+///
+/// Fields which are not present in the parsed value default to the start of
+/// the period (e.g. `2003-12` is the first day of December at midnight).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    offset: Option<i32>,
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn digits(&mut self, count: usize) -> Option<u32> {
+        let digits = self.bytes.get(self.pos..self.pos + count)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos += count;
+        Some(
+            digits
+                .iter()
+                .fold(0, |acc, b| acc * 10 + u32::from(b - b'0')),
+        )
+    }
+
+    fn is_end(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+}
+
+#[must_use]
+const fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+#[must_use]
+pub(crate) const fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 31,
+    }
+}
+
+/// Number of days since 1970-01-01 for the given civil date.
+#[must_use]
+pub(crate) fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[cfg(feature = "alloc")]
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Time zone names from RFC 822 and their offset from UTC in hours.
+const ZONES: [(&str, i32); 12] = [
+    ("UT", 0),
+    ("UTC", 0),
+    ("GMT", 0),
+    ("Z", 0),
+    ("EST", -5),
+    ("EDT", -4),
+    ("CST", -6),
+    ("CDT", -5),
+    ("MST", -7),
+    ("MDT", -6),
+    ("PST", -8),
+    ("PDT", -7),
+];
+
+/// Parses a number of one or more ASCII digits.
+#[must_use]
+fn parse_digits(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Parses an RFC 822 time zone.
+#[must_use]
+fn parse_zone(zone: &str) -> Option<i32> {
+    if let Some(offset) = zone.strip_prefix(['+', '-']) {
+        if offset.len() != 4 {
+            return None;
+        }
+        let hours = parse_digits(&offset[..2])?;
+        let minutes = parse_digits(&offset[2..])?;
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+        let offset = i32::try_from(hours * 3600 + minutes * 60).ok()?;
+        return Some(if zone.starts_with('-') {
+            -offset
+        } else {
+            offset
+        });
+    }
+    ZONES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(zone))
+        .map(|(_, hours)| hours * 3600)
+}
+
+impl DateTime {
+    /// The Unix epoch, 1970-01-01T00:00:00Z.
+    #[cfg(feature = "alloc")]
+    pub(crate) const UNIX_EPOCH: Self = Self {
+        year: 1970,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+        offset: Some(0),
+    };
+
+    /// Parses a [W3C Date and Time Formats][w3cdtf] value.
+    ///
+    /// Any of the `YYYY`, `YYYY-MM`, `YYYY-MM-DD`, `YYYY-MM-DDThh:mmTZD`,
+    /// `YYYY-MM-DDThh:mm:ssTZD`, and `YYYY-MM-DDThh:mm:ss.sTZD` forms are
+    /// accepted. Leading and trailing whitespace is ignored, a space may be used
+    /// instead of the `T` separator, and a missing time zone designator is
+    /// accepted.
+    ///
+    /// [w3cdtf]: https://www.w3.org/TR/NOTE-datetime
+    #[must_use]
+    pub fn parse_w3cdtf(input: &str) -> Option<Self> {
+        let mut cursor = Cursor {
+            bytes: input.trim().as_bytes(),
+            pos: 0,
+        };
+
+        let year = i32::try_from(cursor.digits(4)?).ok()?;
+        let mut value = DateTime {
+            year,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+            offset: None,
+        };
+
+        if cursor.is_end() {
+            return Some(value);
+        }
+        if !cursor.eat(b'-') {
+            return None;
+        }
+        value.month = u8::try_from(cursor.digits(2)?).ok()?;
+        if !(1..=12).contains(&value.month) {
+            return None;
+        }
+
+        if cursor.is_end() {
+            return Some(value);
+        }
+        if !cursor.eat(b'-') {
+            return None;
+        }
+        value.day = u8::try_from(cursor.digits(2)?).ok()?;
+        if value.day == 0 || value.day > days_in_month(value.year, value.month) {
+            return None;
+        }
+
+        if cursor.is_end() {
+            return Some(value);
+        }
+        if !(cursor.eat(b'T') || cursor.eat(b't') || cursor.eat(b' ')) {
+            return None;
+        }
+
+        value.hour = u8::try_from(cursor.digits(2)?).ok()?;
+        if !cursor.eat(b':') {
+            return None;
+        }
+        value.minute = u8::try_from(cursor.digits(2)?).ok()?;
+        if cursor.eat(b':') {
+            value.second = u8::try_from(cursor.digits(2)?).ok()?;
+            if cursor.eat(b'.') || cursor.eat(b',') {
+                let mut nanosecond = 0;
+                let mut scale = 100_000_000;
+                let start = cursor.pos;
+                while let Some(b) = cursor.peek().filter(u8::is_ascii_digit) {
+                    nanosecond += u32::from(b - b'0') * scale;
+                    scale /= 10;
+                    cursor.pos += 1;
+                }
+                if cursor.pos == start {
+                    return None;
+                }
+                value.nanosecond = nanosecond;
+            }
+        }
+        // Allow a leap second
+        if value.hour > 23 || value.minute > 59 || value.second > 60 {
+            return None;
+        }
+
+        match cursor.peek() {
+            None => {}
+            Some(b'Z' | b'z') => {
+                cursor.pos += 1;
+                value.offset = Some(0);
+            }
+            Some(sign @ (b'+' | b'-')) => {
+                cursor.pos += 1;
+                let hours = cursor.digits(2)?;
+                cursor.eat(b':');
+                let minutes = cursor.digits(2)?;
+                if hours > 23 || minutes > 59 {
+                    return None;
+                }
+                let offset = i32::try_from(hours * 3600 + minutes * 60).ok()?;
+                value.offset = Some(if sign == b'-' { -offset } else { offset });
+            }
+            Some(_) => return None,
+        }
+
+        cursor.is_end().then_some(value)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn year(&self) -> i32 {
+        self.year
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// The offset from UTC in seconds, if a time zone was given.
+    #[inline]
+    #[must_use]
+    pub const fn offset(&self) -> Option<i32> {
+        self.offset
+    }
+
+    /// Parses an [RFC 822][rfc822] date and time as used in RSS.
+    ///
+    /// The day of the week is optional and is not checked against the date.
+    /// Seconds are optional. Two digit years are in the range 1950 to 2049 and
+    /// three digit years are added to 1900. Month names may be in any ASCII
+    /// case and may be spelled out (e.g. `January`). The time zone may be a
+    /// numeric offset, a name such as `GMT` or `EST`, or missing. Military time
+    /// zones other than `Z` are not accepted.
+    ///
+    /// [rfc822]: https://www.rfc-editor.org/rfc/rfc822#section-5
+    #[must_use]
+    pub fn parse_rfc2822(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let input = match input.split_once(',') {
+            Some((weekday, rest)) if weekday.trim().bytes().all(|b| b.is_ascii_alphabetic()) => {
+                rest
+            }
+            _ => input,
+        };
+        let mut parts = input.split_whitespace();
+
+        let day = u8::try_from(parse_digits(parts.next()?)?).ok()?;
+        let month = parts.next()?;
+        let month = MONTHS
+            .iter()
+            .position(|name| {
+                month.len() >= 3
+                    && month.is_char_boundary(3)
+                    && name.eq_ignore_ascii_case(&month[..3])
+            })
+            .and_then(|index| u8::try_from(index + 1).ok())?;
+        let year = parts.next()?;
+        let year = match (year.len(), i32::try_from(parse_digits(year)?).ok()?) {
+            (2, year) if year < 50 => year + 2000,
+            (2 | 3, year) => year + 1900,
+            (4, year) => year,
+            _ => return None,
+        };
+        if day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        let mut time = parts.next()?.split(':');
+        let hour = u8::try_from(parse_digits(time.next()?)?).ok()?;
+        let minute = u8::try_from(parse_digits(time.next()?)?).ok()?;
+        let second = match time.next() {
+            Some(second) => u8::try_from(parse_digits(second)?).ok()?,
+            None => 0,
+        };
+        // Allow a leap second
+        if time.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let offset = match parts.next() {
+            Some(zone) => Some(parse_zone(zone)?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond: 0,
+            offset,
+        })
+    }
+
+    /// Number of non-leap seconds since the Unix epoch.
+    ///
+    /// Values without a time zone are treated as UTC.
+    #[must_use]
+    pub fn unix_timestamp(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days * 86_400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+            - i64::from(self.offset.unwrap_or_default())
+    }
+
+    /// Writes the value in the [W3C Date and Time Formats][w3cdtf] profile.
+    ///
+    /// Values without a time zone are written as UTC.
+    ///
+    /// [w3cdtf]: https://www.w3.org/TR/NOTE-datetime
+    #[cfg(feature = "alloc")]
+    pub(crate) fn write_w3cdtf<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(
+            w,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.nanosecond != 0 {
+            let fraction = trim_fraction(self.nanosecond);
+            write!(w, ".{:0width$}", fraction.0, width = fraction.1)?;
+        }
+        match self.offset.unwrap_or_default() {
+            0 => w.write_char('Z'),
+            offset => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                write!(w, "{sign}{:02}:{:02}", offset / 3600, offset % 3600 / 60)
+            }
+        }
+    }
+
+    /// Writes the value in the [RFC 822][rfc822] format with a four digit year.
+    ///
+    /// Values without a time zone are written as UTC.
+    ///
+    /// [rfc822]: https://www.rfc-editor.org/rfc/rfc822#section-5
+    #[cfg(feature = "alloc")]
+    pub(crate) fn write_rfc2822<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let days = days_from_civil(self.year, self.month, self.day);
+        // 1970-01-01 was a Thursday
+        let weekday = usize::try_from((days + 4).rem_euclid(7)).unwrap_or_default();
+        let offset = self.offset.unwrap_or_default();
+        let sign = if offset < 0 { '-' } else { '+' };
+        let offset = offset.unsigned_abs();
+        write!(
+            w,
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} {sign}{:02}{:02}",
+            WEEKDAYS[weekday],
+            self.day,
+            MONTHS[usize::from(self.month - 1)],
+            self.year,
+            self.hour,
+            self.minute,
+            self.second,
+            offset / 3600,
+            offset % 3600 / 60
+        )
+    }
+}
+
+/// The nanoseconds without trailing zeros and the number of digits to write.
+#[cfg(feature = "alloc")]
+#[must_use]
+const fn trim_fraction(nanosecond: u32) -> (u32, usize) {
+    let mut fraction = nanosecond;
+    let mut width = 9;
+    while fraction % 10 == 0 && width > 1 {
+        fraction /= 10;
+        width -= 1;
+    }
+    (fraction, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_w3cdtf() {
+        let value = DateTime::parse_w3cdtf("1997-07-16T19:20:30.45+01:00").unwrap();
+        assert_eq!(1997, value.year());
+        assert_eq!(7, value.month());
+        assert_eq!(16, value.day());
+        assert_eq!(19, value.hour());
+        assert_eq!(20, value.minute());
+        assert_eq!(30, value.second());
+        assert_eq!(450_000_000, value.nanosecond());
+        assert_eq!(Some(3600), value.offset());
+        assert_eq!(869_077_230, value.unix_timestamp());
+
+        let value = DateTime::parse_w3cdtf("2021-02-24T09:08:10Z").unwrap();
+        assert_eq!(Some(0), value.offset());
+        assert_eq!(1_614_157_690, value.unix_timestamp());
+
+        let value = DateTime::parse_w3cdtf(" 2003-12 ").unwrap();
+        assert_eq!((2003, 12, 1), (value.year(), value.month(), value.day()));
+        assert_eq!(None, value.offset());
+
+        let value = DateTime::parse_w3cdtf("1969-12-31T23:59-05:00").unwrap();
+        assert_eq!(17_940, value.unix_timestamp());
+
+        for input in [
+            "",
+            "97",
+            "2003-13",
+            "2003-02-29",
+            "2003-12-01T",
+            "2003-12-01T25:00Z",
+            "2003-12-01T10:00:00.Z",
+            "2003-12-01T10:00:00+0",
+            "2003-12-01T10:00:00Zjunk",
+        ] {
+            assert_eq!(None, DateTime::parse_w3cdtf(input), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_rfc2822() {
+        let value = DateTime::parse_rfc2822("Sat, 07 Sep 2002 09:42:31 GMT").unwrap();
+        assert_eq!((2002, 9, 7), (value.year(), value.month(), value.day()));
+        assert_eq!((9, 42, 31), (value.hour(), value.minute(), value.second()));
+        assert_eq!(Some(0), value.offset());
+        assert_eq!(1_031_391_751, value.unix_timestamp());
+
+        let value = DateTime::parse_rfc2822(" 7 september 02 09:42 -0500 ").unwrap();
+        assert_eq!((2002, 9, 7), (value.year(), value.month(), value.day()));
+        assert_eq!(Some(-18_000), value.offset());
+
+        let value = DateTime::parse_rfc2822("Tue, 10 Jun 2003 04:00:00 EDT").unwrap();
+        assert_eq!(Some(-14_400), value.offset());
+
+        let value = DateTime::parse_rfc2822("10 Jun 99 04:00").unwrap();
+        assert_eq!(1999, value.year());
+        assert_eq!(None, value.offset());
+
+        for input in [
+            "",
+            "2002-09-07T09:42:31Z",
+            "Sat, 31 Sep 2002 09:42:31 GMT",
+            "Sat, 07 Sep 2002 24:00:00 GMT",
+            "Sat, 07 Sep 2002 09:42:31 ABC",
+            "Sat, 07 Sep 2002 09:42:31 +05",
+            "Sat, 07 Sep 2002 09:42:31 GMT junk",
+        ] {
+            assert_eq!(None, DateTime::parse_rfc2822(input), "{input}");
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_date_time() {
+        use alloc::string::String;
+
+        let mut output = String::new();
+        let value = DateTime::parse_w3cdtf("1997-07-16T19:20:30.45+01:00").unwrap();
+        value.write_w3cdtf(&mut output).unwrap();
+        output.push('|');
+        value.write_rfc2822(&mut output).unwrap();
+        output.push('|');
+        let value = DateTime::parse_rfc2822("Sat, 07 Sep 2002 09:42:31 -0330").unwrap();
+        value.write_w3cdtf(&mut output).unwrap();
+        output.push('|');
+        value.write_rfc2822(&mut output).unwrap();
+        assert_eq!(
+            "1997-07-16T19:20:30.45+01:00|Wed, 16 Jul 1997 19:20:30 +0100|\
+            2002-09-07T09:42:31-03:30|Sat, 07 Sep 2002 09:42:31 -0330",
+            output
+        );
+    }
+}
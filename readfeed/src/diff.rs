@@ -0,0 +1,539 @@
+//! Compares two fetches of the same feed.
+//!
+//! Items and entries are matched by their [`Identity`]. If an identity occurs
+//! more than once in a document, only the first occurrence is used.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::diff;
+//!
+//! let old = "
+//! <rss>
+//!     <channel>
+//!         <item><guid>1</guid><title>Lorem</title></item>
+//!         <item><guid>2</guid><title>Ipsum</title></item>
+//!     </channel>
+//! </rss>
+//! ";
+//! let new = "
+//! <rss>
+//!     <channel>
+//!         <item><guid>3</guid><title>Dolor</title></item>
+//!         <item><guid>1</guid><title>Lorem (updated)</title></item>
+//!     </channel>
+//! </rss>
+//! ";
+//!
+//! let diff = diff::diff(old, new)?;
+//! assert_eq!(vec!["3"], diff.added().iter().map(|id| id.key()).collect::<Vec<_>>());
+//! assert_eq!(vec!["2"], diff.removed().iter().map(|id| id.key()).collect::<Vec<_>>());
+//!
+//! let modified = &diff.modified()[0];
+//! assert_eq!("1", modified.identity().key());
+//! assert!(modified.changes().title());
+//! assert!(!modified.changes().content());
+//! # Ok::<(), diff::DiffError>(())
+//! ```
+
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    atom,
+    ext::content,
+    identity::{decode_non_empty, FieldDate, Fields, Identity},
+    rss, Ty,
+};
+
+/// An error from comparing two documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiffError {
+    /// The documents are of different types.
+    MismatchedTypes {
+        /// The type of the old document.
+        old: Ty,
+        /// The type of the new document.
+        new: Ty,
+    },
+    /// The documents are not RSS or Atom feeds.
+    UnsupportedType(Ty),
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffError::MismatchedTypes { old, new } => {
+                write!(f, "documents are of different types ({old:?} and {new:?})")
+            }
+            DiffError::UnsupportedType(ty) => {
+                write!(f, "documents of type {ty:?} are not supported")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DiffError {}
+
+/// The fields which changed in a modified item or entry.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Changes {
+    title: bool,
+    content: bool,
+    updated: bool,
+    enclosures: bool,
+}
+
+impl Changes {
+    /// If the `title` changed.
+    #[inline]
+    #[must_use]
+    pub const fn title(&self) -> bool {
+        self.title
+    }
+
+    /// If the content changed.
+    ///
+    /// For RSS items, the content is the `description` and `content:encoded`
+    /// elements. For Atom entries, the content is the `content` and `summary`
+    /// elements.
+    #[inline]
+    #[must_use]
+    pub const fn content(&self) -> bool {
+        self.content
+    }
+
+    /// If the `pubDate` of an RSS item or the `updated` date of an Atom entry
+    /// changed.
+    ///
+    /// If an RSS item has no `pubDate` element, the `dc:date` is used. If an
+    /// Atom entry has no `updated` element, the `published` date is used.
+    /// Dates are compared as instants, so a date written with a different time
+    /// zone is not a change.
+    #[inline]
+    #[must_use]
+    pub const fn updated(&self) -> bool {
+        self.updated
+    }
+
+    /// If the enclosure URLs changed.
+    ///
+    /// For RSS items, the enclosures are the `enclosure` elements. For Atom
+    /// entries, the enclosures are the `link` elements with the `enclosure`
+    /// relation.
+    #[inline]
+    #[must_use]
+    pub const fn enclosures(&self) -> bool {
+        self.enclosures
+    }
+
+    /// If no fields changed.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        !(self.title || self.content || self.updated || self.enclosures)
+    }
+}
+
+/// An item or entry which is in both documents but has changed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Modified<'a> {
+    identity: Identity<'a>,
+    changes: Changes,
+}
+
+impl<'a> Modified<'a> {
+    /// The identity of the item or entry.
+    #[inline]
+    #[must_use]
+    pub const fn identity(&self) -> &Identity<'a> {
+        &self.identity
+    }
+
+    /// The fields which changed.
+    #[inline]
+    #[must_use]
+    pub const fn changes(&self) -> Changes {
+        self.changes
+    }
+}
+
+/// The differences between two documents.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Diff<'a> {
+    added: Vec<Identity<'a>>,
+    removed: Vec<Identity<'a>>,
+    modified: Vec<Modified<'a>>,
+}
+
+impl<'a> Diff<'a> {
+    /// The items or entries which are only in the new document, in the order
+    /// of the new document.
+    #[inline]
+    #[must_use]
+    pub fn added(&self) -> &[Identity<'a>] {
+        &self.added
+    }
+
+    /// The items or entries which are only in the old document, in the order
+    /// of the old document.
+    #[inline]
+    #[must_use]
+    pub fn removed(&self) -> &[Identity<'a>] {
+        &self.removed
+    }
+
+    /// The items or entries which are in both documents and have changed, in
+    /// the order of the new document.
+    #[inline]
+    #[must_use]
+    pub fn modified(&self) -> &[Modified<'a>] {
+        &self.modified
+    }
+
+    /// If the documents have the same items or entries and none have changed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// The compared fields of an item or entry.
+#[derive(Debug)]
+struct Snapshot<'a> {
+    identity: Identity<'a>,
+    title: Option<Cow<'a, str>>,
+    content: Vec<Cow<'a, str>>,
+    updated: Option<FieldDate<'a>>,
+    enclosures: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Snapshot<'a> {
+    #[must_use]
+    fn from_item(item: rss::ChannelItemIter<'a>) -> Self {
+        let mut snapshot = Snapshot::new(Fields::from_item(item.clone()));
+        for elem in item {
+            match elem {
+                rss::ItemElem::Description(desc) => {
+                    snapshot.content.extend(decode_non_empty(desc.content()));
+                }
+                rss::ItemElem::Content(content::Elem::Encoded(encoded)) => {
+                    snapshot.content.extend(decode_non_empty(encoded.content()));
+                }
+                _ => {}
+            }
+        }
+        snapshot
+    }
+
+    #[must_use]
+    fn from_entry(entry: atom::EntryIter<'a>) -> Self {
+        let mut snapshot = Snapshot::new(Fields::from_entry(entry.clone()));
+        for elem in entry {
+            match elem {
+                atom::EntryElem::Content(content) => {
+                    snapshot.content.extend(decode_non_empty(content.content()));
+                }
+                atom::EntryElem::Summary(summary) => {
+                    snapshot.content.extend(decode_non_empty(summary.content()));
+                }
+                _ => {}
+            }
+        }
+        snapshot
+    }
+
+    #[must_use]
+    fn new(fields: Fields<'a>) -> Self {
+        Self {
+            identity: fields.identity(),
+            title: fields.title,
+            content: Vec::new(),
+            updated: fields.updated.or(fields.published),
+            enclosures: fields.enclosures,
+        }
+    }
+
+    #[must_use]
+    fn changes(&self, new: &Self) -> Changes {
+        Changes {
+            title: self.title != new.title,
+            content: self.content != new.content,
+            updated: !is_same_date(self.updated.as_ref(), new.updated.as_ref()),
+            enclosures: self.enclosures != new.enclosures,
+        }
+    }
+}
+
+/// Returns true if the dates are the same instant.
+///
+/// Dates which cannot be parsed are compared as written.
+#[must_use]
+fn is_same_date(a: Option<&FieldDate<'_>>, b: Option<&FieldDate<'_>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.date_time, b.date_time) {
+            (Some(a), Some(b)) => a.unix_timestamp() == b.unix_timestamp(),
+            _ => a.value == b.value,
+        },
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Collects the snapshots of the items or entries of a document.
+#[must_use]
+fn snapshots(input: &str, ty: Ty) -> Vec<Snapshot<'_>> {
+    match ty {
+        Ty::Atom => atom::Iter::new(input)
+            .filter_map(|elem| match elem {
+                atom::Elem::Feed(feed) => Some(feed),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|elem| match elem {
+                atom::FeedElem::Entry(entry) => Some(Snapshot::from_entry(entry)),
+                _ => None,
+            })
+            .collect(),
+        Ty::Rss => rss::Iter::new(input)
+            .filter_map(|elem| match elem {
+                rss::Elem::Rss(rss) => Some(rss),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|elem| match elem {
+                rss::RssElem::Channel(channel) => Some(channel),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|elem| match elem {
+                rss::ChannelElem::Item(item) => Some(Snapshot::from_item(item)),
+                _ => None,
+            })
+            .collect(),
+        Ty::Json | Ty::Unknown | Ty::XmlOrHtml => Vec::new(),
+    }
+}
+
+/// Compares the items or entries of two fetches of the same RSS or Atom feed.
+///
+/// # Errors
+///
+/// Returns an error if the documents are of different types or are not RSS or
+/// Atom feeds. The type of each document is detected with
+/// [`detect_type()`][crate::detect_type].
+pub fn diff<'a>(old: &'a str, new: &'a str) -> Result<Diff<'a>, DiffError> {
+    let old_ty = crate::detect_type(old);
+    let new_ty = crate::detect_type(new);
+    if old_ty != new_ty {
+        return Err(DiffError::MismatchedTypes {
+            old: old_ty,
+            new: new_ty,
+        });
+    }
+    if !matches!(new_ty, Ty::Atom | Ty::Rss) {
+        return Err(DiffError::UnsupportedType(new_ty));
+    }
+
+    let old = snapshots(old, old_ty);
+    let mut old_by_identity = BTreeMap::new();
+    for snapshot in &old {
+        old_by_identity
+            .entry(&snapshot.identity)
+            .or_insert(snapshot);
+    }
+
+    let mut diff = Diff::default();
+    let mut seen = BTreeSet::new();
+    for snapshot in snapshots(new, new_ty) {
+        if seen.contains(&snapshot.identity) {
+            continue;
+        }
+        match old_by_identity.get(&snapshot.identity) {
+            Some(old) => {
+                let changes = old.changes(&snapshot);
+                if !changes.is_empty() {
+                    diff.modified.push(Modified {
+                        identity: snapshot.identity.clone(),
+                        changes,
+                    });
+                }
+            }
+            None => diff.added.push(snapshot.identity.clone()),
+        }
+        seen.insert(snapshot.identity);
+    }
+
+    for snapshot in &old {
+        if seen.insert(snapshot.identity.clone()) {
+            diff.removed.push(snapshot.identity.clone());
+        }
+    }
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn diff_entries() {
+        let old = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <entry>
+        <id>1</id>
+        <title>Lorem</title>
+        <updated>2024-01-01T00:00:00Z</updated>
+        <content>Lorem ipsum</content>
+    </entry>
+    <entry>
+        <id>2</id>
+        <title>Ipsum</title>
+        <link rel="enclosure" href="https://example.com/2.mp3"/>
+    </entry>
+    <entry>
+        <id>3</id>
+    </entry>
+    <entry>
+        <id>3</id>
+    </entry>
+</feed>
+        "#;
+        let new = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <entry>
+        <id>4</id>
+    </entry>
+    <entry>
+        <id>2</id>
+        <title>Ipsum</title>
+        <link rel="enclosure" href="https://example.com/2.m4a"/>
+    </entry>
+    <entry>
+        <id>1</id>
+        <title>Lorem</title>
+        <updated>2024-01-02T00:00:00Z</updated>
+        <content>Lorem ipsum dolor</content>
+    </entry>
+    <entry>
+        <id>4</id>
+    </entry>
+</feed>
+        "#;
+
+        let diff = diff(old, new).unwrap();
+        assert_eq!(
+            vec!["id:4"],
+            diff.added()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["id:3"],
+            diff.removed()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+
+        let modified = diff.modified();
+        assert_eq!(2, modified.len());
+        assert_eq!("2", modified[0].identity().key());
+        assert_eq!(
+            Changes {
+                enclosures: true,
+                ..Changes::default()
+            },
+            modified[0].changes()
+        );
+        assert_eq!("1", modified[1].identity().key());
+        assert_eq!(
+            Changes {
+                content: true,
+                updated: true,
+                ..Changes::default()
+            },
+            modified[1].changes()
+        );
+
+        assert!(super::diff(old, old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn diff_items() {
+        let old = r#"
+<rss xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <channel>
+        <item>
+            <guid>1</guid>
+            <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+        <item>
+            <guid>2</guid>
+            <dc:date>2024-01-01T00:00:00Z</dc:date>
+        </item>
+        <item>
+            <guid>3</guid>
+            <pubDate>yesterday</pubDate>
+        </item>
+    </channel>
+</rss>
+        "#;
+        let new = r#"
+<rss xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <channel>
+        <item>
+            <guid>1</guid>
+            <pubDate>Mon, 01 Jan 2024 01:00:00 +0100</pubDate>
+        </item>
+        <item>
+            <guid>2</guid>
+            <dc:date>2024-01-02T00:00:00Z</dc:date>
+        </item>
+        <item>
+            <guid>3</guid>
+            <pubDate>today</pubDate>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let diff = diff(old, new).unwrap();
+        assert!(diff.added().is_empty());
+        assert!(diff.removed().is_empty());
+        assert_eq!(
+            vec!["2", "3"],
+            diff.modified()
+                .iter()
+                .map(|modified| modified.identity().key())
+                .collect::<Vec<_>>()
+        );
+        assert!(diff
+            .modified()
+            .iter()
+            .all(|modified| modified.changes().updated()));
+    }
+
+    #[test]
+    fn diff_errors() {
+        assert_eq!(
+            Err(DiffError::MismatchedTypes {
+                old: Ty::Rss,
+                new: Ty::Atom
+            }),
+            diff("<rss></rss>", "<feed></feed>")
+        );
+        assert_eq!(Err(DiffError::UnsupportedType(Ty::Json)), diff("{}", "{}"));
+    }
+}
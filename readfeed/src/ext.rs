@@ -0,0 +1,24 @@
+//! Namespaced extensions which may be embedded in RSS and Atom documents.
+//!
+//! Extension elements are identified by their conventional namespace prefix
+//! (e.g. `media:` for [Media RSS][media]). They are returned as dedicated
+//! variants from the RSS and Atom iterators instead of as unknown elements.
+
+use maybe_xml::token::prop::TagName;
+
+pub mod content;
+pub mod dc;
+pub mod fh;
+pub mod itunes;
+pub mod media;
+pub mod podcast;
+pub mod sy;
+pub mod thr;
+
+#[must_use]
+pub(crate) fn has_prefix(tag_name: TagName<'_>, prefix: &str) -> bool {
+    tag_name
+        .namespace_prefix()
+        .map(|p| p.as_str().eq_ignore_ascii_case(prefix))
+        .unwrap_or_default()
+}
@@ -0,0 +1,269 @@
+//! The [RSS content module][content] carries the full content of an item,
+//! usually as escaped HTML or HTML in a `CDATA` section.
+//!
+//! Content elements are returned as
+//! [`rss::ItemElem::Content`][crate::rss::ItemElem::Content].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::ext::content;
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:content="http://purl.org/rss/1.0/modules/content/">
+//!     <channel>
+//!         <item>
+//!             <description>Teaser</description>
+//!             <content:encoded><![CDATA[<p>Full article</p>]]></content:encoded>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! assert!(matches!(item_iter.next(), Some(ItemElem::Description(_))));
+//!
+//! if let Some(ItemElem::Content(content::Elem::Encoded(encoded))) = item_iter.next() {
+//!     assert_eq!("<![CDATA[<p>Full article</p>]]>", encoded.content());
+//!     # #[cfg(feature = "alloc")]
+//!     assert_eq!("<p>Full article</p>", encoded.html());
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [content]: https://web.resource.org/rss/1.0/modules/content/
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+use maybe_xml::token::{
+    prop::{Attributes, TagName},
+    Token,
+};
+
+#[cfg(feature = "html-sanitize")]
+use crate::sanitize::Policy;
+use crate::{uri::Base, xml::Scope, Tag};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+            scope: Scope<'a>,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+
+            /// The `xml:base` values which apply to the element.
+            #[inline]
+            #[must_use]
+            pub const fn base(&self) -> Base<'a> {
+                self.scope.base()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(Encoded);
+
+impl<'a> Encoded<'a> {
+    /// The decoded HTML.
+    ///
+    /// Entity references are replaced and `CDATA` sections are unwrapped.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn html(&self) -> Cow<'a, str> {
+        crate::xml::decode(self.content)
+    }
+
+    /// The decoded HTML as plain text.
+    ///
+    /// See [`text::html_to_text()`][crate::text::html_to_text].
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        crate::text::html_to_text(&self.html())
+    }
+
+    /// The decoded HTML sanitized with the policy.
+    ///
+    /// Relative URLs are resolved against the base URI of the element.
+    #[cfg(feature = "html-sanitize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+    #[must_use]
+    pub fn to_sanitized_html(&self, document_url: &str, policy: &Policy) -> String {
+        policy.sanitize(&self.html(), Some(&self.scope.base().to_uri(document_url)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    Encoded(Encoded<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
+                }
+            };
+        }
+
+        return_content!("encoded", Encoded, Elem::Encoded);
+
+        Elem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
+    }
+}
+
+#[must_use]
+pub(crate) fn is_content(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "content")
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "html-sanitize")]
+    use alloc::{vec, vec::Vec};
+
+    use crate::rss::{self, ChannelElem, ItemElem, RssElem};
+
+    #[test]
+    fn eval_content_encoded() {
+        let input = r#"
+<rss xmlns:content="http://purl.org/rss/1.0/modules/content/">
+    <channel>
+        <item>
+            <content:encoded>&lt;p&gt;Lorem &amp;amp; ipsum&lt;/p&gt;</content:encoded>
+            <content:encoded>
+                <![CDATA[<p>Dolor</p>]]>
+            </content:encoded>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ItemElem::Content(Elem::Encoded(encoded))) = item_iter.next() {
+            assert_eq!("<p>Lorem &amp; ipsum</p>", encoded.html());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Content(Elem::Encoded(encoded))) = item_iter.next() {
+            assert_eq!("<p>Dolor</p>", encoded.html().trim());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, item_iter.next());
+    }
+
+    #[cfg(feature = "html-sanitize")]
+    #[test]
+    fn eval_sanitized_html() {
+        let input = r#"
+<rss xmlns:content="http://purl.org/rss/1.0/modules/content/">
+    <channel xml:base="https://example.com/blog/">
+        <item xml:base="2024/">
+            <content:encoded>&lt;img src="a.png" onerror="x()"&gt;&lt;script&gt;x()&lt;/script&gt;</content:encoded>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ItemElem::Content(Elem::Encoded(encoded))) = item_iter.next() {
+            assert_eq!(
+                vec!["https://example.com/blog/", "2024/"],
+                encoded.base().iter().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                r#"<img src="https://example.com/blog/2024/a.png">"#,
+                encoded.to_sanitized_html("https://example.com/feed.xml", &Policy::new())
+            );
+        } else {
+            panic!();
+        }
+    }
+}
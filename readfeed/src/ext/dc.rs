@@ -0,0 +1,274 @@
+//! [Dublin Core][dc] metadata elements and common [DCMI terms][dcterms].
+//!
+//! Elements with either the `dc:` or the `dcterms:` prefix are returned as
+//! `Dc` variants from the RSS channel and item iterators and the Atom feed and
+//! entry iterators.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::ext::dc;
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:dc="http://purl.org/dc/elements/1.1/">
+//!     <channel>
+//!         <item>
+//!             <dc:creator>Jane Doe</dc:creator>
+//!             <dc:date>2021-02-24T09:08:10Z</dc:date>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ItemElem::Dc(dc::Elem::Creator(creator))) = item_iter.next() {
+//!     assert_eq!("Jane Doe", creator.content());
+//! } else {
+//!     panic!();
+//! }
+//! if let Some(ItemElem::Dc(dc::Elem::Date(date))) = item_iter.next() {
+//!     assert_eq!(Some(1_614_157_690), date.to_date_time().map(|d| d.unix_timestamp()));
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [dc]: https://www.dublincore.org/specifications/dublin-core/dces/
+//! [dcterms]: https://www.dublincore.org/specifications/dublin-core/dcmi-terms/
+
+use maybe_xml::token::{
+    prop::{Attributes, TagName},
+    Token,
+};
+
+use crate::{date::DateTime, Tag};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+macro_rules! impl_date_construct {
+    ($name:ident $(,)?) => {
+        content_elem!($name);
+
+        impl<'a> $name<'a> {
+            /// Parses the content as a W3C date and time.
+            #[inline]
+            #[must_use]
+            pub fn to_date_time(&self) -> Option<DateTime> {
+                DateTime::parse_w3cdtf(self.content)
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        impl_date_construct!($name);
+        impl_date_construct!($($nms),+);
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(
+    Title,
+    Creator,
+    Subject,
+    Description,
+    Publisher,
+    Contributor,
+    Ty,
+    Format,
+    Identifier,
+    Source,
+    Language,
+    Relation,
+    Coverage,
+    Rights,
+    License,
+);
+
+impl_date_construct!(Date, Modified, Created, Issued);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    Title(Title<'a>),
+    Creator(Creator<'a>),
+    Subject(Subject<'a>),
+    Description(Description<'a>),
+    Publisher(Publisher<'a>),
+    Contributor(Contributor<'a>),
+    Date(Date<'a>),
+    Ty(Ty<'a>),
+    Format(Format<'a>),
+    Identifier(Identifier<'a>),
+    Source(Source<'a>),
+    Language(Language<'a>),
+    Relation(Relation<'a>),
+    Coverage(Coverage<'a>),
+    Rights(Rights<'a>),
+    Modified(Modified<'a>),
+    Created(Created<'a>),
+    Issued(Issued<'a>),
+    License(License<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("title", Title, Elem::Title);
+        return_content!("creator", Creator, Elem::Creator);
+        return_content!("subject", Subject, Elem::Subject);
+        return_content!("description", Description, Elem::Description);
+        return_content!("publisher", Publisher, Elem::Publisher);
+        return_content!("contributor", Contributor, Elem::Contributor);
+        return_content!("date", Date, Elem::Date);
+        return_content!("type", Ty, Elem::Ty);
+        return_content!("format", Format, Elem::Format);
+        return_content!("identifier", Identifier, Elem::Identifier);
+        return_content!("source", Source, Elem::Source);
+        return_content!("language", Language, Elem::Language);
+        return_content!("relation", Relation, Elem::Relation);
+        return_content!("coverage", Coverage, Elem::Coverage);
+        return_content!("rights", Rights, Elem::Rights);
+
+        return_content!("modified", Modified, Elem::Modified);
+        return_content!("created", Created, Elem::Created);
+        return_content!("issued", Issued, Elem::Issued);
+        return_content!("license", License, Elem::License);
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+/// Returns true if the tag name uses the `dc` or `dcterms` namespace prefix.
+#[must_use]
+pub(crate) fn is_dc(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "dc") || super::has_prefix(tag_name, "dcterms")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::atom::{self, EntryElem, FeedElem};
+
+    #[test]
+    fn eval_dc_in_atom() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+    <dc:rights>CC BY 4.0</dc:rights>
+    <entry>
+        <dc:creator>Jane Doe</dc:creator>
+        <dc:subject>Lorem</dc:subject>
+        <dcterms:modified>2021-02-24T10:00:00+01:00</dcterms:modified>
+        <dcterms:license>https://creativecommons.org/licenses/by/4.0/</dcterms:license>
+        <dc:date>not a date</dc:date>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = atom::Iter::new(input);
+
+        let Some(atom::Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+
+        if let Some(FeedElem::Dc(Elem::Rights(rights))) = feed_iter.next() {
+            assert_eq!("CC BY 4.0", rights.content());
+        } else {
+            panic!();
+        }
+
+        let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+
+        if let Some(EntryElem::Dc(Elem::Creator(creator))) = entry_iter.next() {
+            assert_eq!("Jane Doe", creator.content());
+        } else {
+            panic!();
+        }
+        if let Some(EntryElem::Dc(Elem::Subject(subject))) = entry_iter.next() {
+            assert_eq!("Lorem", subject.content());
+        } else {
+            panic!();
+        }
+        if let Some(EntryElem::Dc(Elem::Modified(modified))) = entry_iter.next() {
+            assert_eq!(
+                Some(1_614_157_200),
+                modified.to_date_time().map(|d| d.unix_timestamp())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(EntryElem::Dc(Elem::License(license))) = entry_iter.next() {
+            assert_eq!(
+                "https://creativecommons.org/licenses/by/4.0/",
+                license.content()
+            );
+        } else {
+            panic!();
+        }
+        if let Some(EntryElem::Dc(Elem::Date(date))) = entry_iter.next() {
+            assert_eq!(None, date.to_date_time());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, entry_iter.next());
+        assert_eq!(None, feed_iter.next());
+    }
+}
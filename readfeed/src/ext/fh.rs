@@ -0,0 +1,399 @@
+//! [Feed Paging and Archiving][fh] describes how to navigate a feed's history.
+//!
+//! The `fh:complete` and `fh:archive` markers are returned as `Fh` variants
+//! from the Atom feed and RSS channel iterators. [`Pagination`] collects the
+//! navigation links and markers of a feed document.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::atom::{self, Elem};
+//! use readfeed::ext::fh::{History, Pagination};
+//!
+//! let input = r#"
+//! <feed xmlns="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0">
+//!     <link rel="current" href="https://example.com/feed"/>
+//!     <link rel="prev-archive" href="https://example.com/archive/2"/>
+//!     <fh:archive/>
+//! </feed>
+//! "#;
+//!
+//! let mut iter = atom::Iter::new(input);
+//!
+//! let Some(Elem::Feed(feed_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//!
+//! let pagination = Pagination::from_feed(feed_iter);
+//! assert_eq!(History::Archived, pagination.history());
+//! assert!(pagination.is_archive());
+//! assert_eq!(Some("https://example.com/feed"), pagination.current());
+//! assert_eq!(Some("https://example.com/archive/2"), pagination.prev_archive());
+//! ```
+//!
+//! [fh]: https://datatracker.ietf.org/doc/html/rfc5005
+
+use maybe_xml::token::{
+    prop::{Attributes, TagName},
+    Token,
+};
+
+use crate::{atom, rss, Tag};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(Complete, Archive);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    Complete(Complete<'a>),
+    Archive(Archive<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("complete", Complete, Elem::Complete);
+        return_content!("archive", Archive, Elem::Archive);
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+#[must_use]
+pub(crate) fn is_fh(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "fh")
+}
+
+/// Link relations used to navigate a feed's history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rel {
+    First,
+    Last,
+    Next,
+    Previous,
+    PrevArchive,
+    NextArchive,
+    Current,
+}
+
+impl Rel {
+    /// Classifies a single link relation.
+    ///
+    /// The IANA registered relation URIs and the `prev` synonym are also
+    /// accepted.
+    #[must_use]
+    pub fn classify(rel: &str) -> Option<Self> {
+        let rel = rel.trim();
+        let rel = rel
+            .strip_prefix("http://www.iana.org/assignments/relation/")
+            .unwrap_or(rel);
+
+        [
+            ("first", Rel::First),
+            ("last", Rel::Last),
+            ("next", Rel::Next),
+            ("previous", Rel::Previous),
+            ("prev", Rel::Previous),
+            ("prev-archive", Rel::PrevArchive),
+            ("next-archive", Rel::NextArchive),
+            ("current", Rel::Current),
+        ]
+        .into_iter()
+        .find_map(|(name, value)| rel.eq_ignore_ascii_case(name).then_some(value))
+    }
+}
+
+/// The kind of history a feed document describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum History {
+    /// The document contains all of the entries. Its previous entries may be
+    /// removed.
+    Complete,
+    /// The entries are split across archive documents which are found by
+    /// following the `prev-archive` links.
+    Archived,
+    /// The entries are split across pages which are found by following the
+    /// `next` links.
+    Paged,
+    /// The document does not describe its history.
+    Unknown,
+}
+
+/// The navigation links and markers of a feed document.
+///
+/// To reconstruct the full history of an archived feed, fetch the subscription
+/// document and follow [`prev_archive()`][Pagination::prev_archive] until
+/// there are no more links. For a paged feed, follow [`next()`][Pagination::next]
+/// instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pagination<'a> {
+    first: Option<&'a str>,
+    last: Option<&'a str>,
+    next: Option<&'a str>,
+    previous: Option<&'a str>,
+    prev_archive: Option<&'a str>,
+    next_archive: Option<&'a str>,
+    current: Option<&'a str>,
+    is_complete: bool,
+    is_archive: bool,
+}
+
+impl<'a> Pagination<'a> {
+    /// Collects the pagination from an Atom feed.
+    #[must_use]
+    pub fn from_feed(feed: atom::FeedIter<'a>) -> Self {
+        let mut pagination = Self::default();
+        for elem in feed {
+            match elem {
+                atom::FeedElem::Link(link) => {
+                    if let (Some(rel), Some(href)) = (link.rel(), link.href()) {
+                        pagination.add_link(rel.as_str(), href.as_str());
+                    }
+                }
+                atom::FeedElem::Fh(elem) => pagination.add_marker(&elem),
+                _ => {}
+            }
+        }
+        pagination
+    }
+
+    /// Collects the pagination from the `atom:link` elements in an RSS channel.
+    #[must_use]
+    pub fn from_channel(channel: rss::ChannelIter<'a>) -> Self {
+        let mut pagination = Self::default();
+        for elem in channel {
+            match elem {
+                rss::ChannelElem::AtomLink(link) => {
+                    if let (Some(rel), Some(href)) = (link.rel(), link.href()) {
+                        pagination.add_link(rel.as_str(), href.as_str());
+                    }
+                }
+                rss::ChannelElem::Fh(elem) => pagination.add_marker(&elem),
+                _ => {}
+            }
+        }
+        pagination
+    }
+
+    fn add_link(&mut self, rel: &str, href: &'a str) {
+        let href = href.trim();
+        for rel in rel.split_ascii_whitespace().filter_map(Rel::classify) {
+            let slot = match rel {
+                Rel::First => &mut self.first,
+                Rel::Last => &mut self.last,
+                Rel::Next => &mut self.next,
+                Rel::Previous => &mut self.previous,
+                Rel::PrevArchive => &mut self.prev_archive,
+                Rel::NextArchive => &mut self.next_archive,
+                Rel::Current => &mut self.current,
+            };
+            slot.get_or_insert(href);
+        }
+    }
+
+    fn add_marker(&mut self, elem: &Elem<'a>) {
+        match elem {
+            Elem::Complete(_) => self.is_complete = true,
+            Elem::Archive(_) => self.is_archive = true,
+            Elem::Unknown(_) | Elem::Raw(_) => {}
+        }
+    }
+
+    /// The link for the given relation.
+    #[must_use]
+    pub const fn link(&self, rel: Rel) -> Option<&'a str> {
+        match rel {
+            Rel::First => self.first,
+            Rel::Last => self.last,
+            Rel::Next => self.next,
+            Rel::Previous => self.previous,
+            Rel::PrevArchive => self.prev_archive,
+            Rel::NextArchive => self.next_archive,
+            Rel::Current => self.current,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn first(&self) -> Option<&'a str> {
+        self.first
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn last(&self) -> Option<&'a str> {
+        self.last
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn next(&self) -> Option<&'a str> {
+        self.next
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn previous(&self) -> Option<&'a str> {
+        self.previous
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn prev_archive(&self) -> Option<&'a str> {
+        self.prev_archive
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn next_archive(&self) -> Option<&'a str> {
+        self.next_archive
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn current(&self) -> Option<&'a str> {
+        self.current
+    }
+
+    /// Returns true if the document has the `fh:complete` marker.
+    #[inline]
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    /// Returns true if the document has the `fh:archive` marker.
+    #[inline]
+    #[must_use]
+    pub const fn is_archive(&self) -> bool {
+        self.is_archive
+    }
+
+    /// Determines the kind of history from the markers and links.
+    #[must_use]
+    pub const fn history(&self) -> History {
+        if self.is_complete {
+            History::Complete
+        } else if self.is_archive || self.prev_archive.is_some() || self.next_archive.is_some() {
+            History::Archived
+        } else if self.next.is_some()
+            || self.previous.is_some()
+            || self.first.is_some()
+            || self.last.is_some()
+        {
+            History::Paged
+        } else {
+            History::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_pagination_from_channel() {
+        let input = r#"
+<rss xmlns:atom="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0">
+    <channel>
+        <link>https://example.com/</link>
+        <atom:link rel="self" href="https://example.com/feed?page=2"/>
+        <atom:link rel="first" href="https://example.com/feed"/>
+        <atom:link rel="prev" href="https://example.com/feed?page=1"/>
+        <atom:link rel="http://www.iana.org/assignments/relation/next" href="https://example.com/feed?page=3"/>
+        <atom:link rel="last" href="https://example.com/feed?page=9"/>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(rss::RssElem::Channel(channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        let pagination = Pagination::from_channel(channel_iter);
+        assert_eq!(History::Paged, pagination.history());
+        assert_eq!(Some("https://example.com/feed"), pagination.first());
+        assert_eq!(
+            Some("https://example.com/feed?page=1"),
+            pagination.previous()
+        );
+        assert_eq!(Some("https://example.com/feed?page=3"), pagination.next());
+        assert_eq!(
+            Some("https://example.com/feed?page=9"),
+            pagination.link(Rel::Last)
+        );
+        assert_eq!(None, pagination.current());
+        assert!(!pagination.is_complete());
+    }
+
+    #[test]
+    fn eval_pagination_complete() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0">
+    <fh:complete/>
+</feed>
+        "#;
+
+        let mut iter = atom::Iter::new(input);
+        let Some(atom::Elem::Feed(feed_iter)) = iter.next() else {
+            panic!();
+        };
+
+        let pagination = Pagination::from_feed(feed_iter);
+        assert!(pagination.is_complete());
+        assert_eq!(History::Complete, pagination.history());
+    }
+}
@@ -0,0 +1,647 @@
+//! [iTunes podcast][itunes] elements describe podcasts for Apple Podcasts and
+//! other podcast directories.
+//!
+//! iTunes elements are returned as
+//! [`rss::ChannelElem::Itunes`][crate::rss::ChannelElem::Itunes] and
+//! [`rss::ItemElem::Itunes`][crate::rss::ItemElem::Itunes].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::ext::itunes;
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+//!     <channel>
+//!         <itunes:explicit>clean</itunes:explicit>
+//!         <item>
+//!             <itunes:duration>01:02:03</itunes:duration>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ChannelElem::Itunes(itunes::Elem::Explicit(explicit))) = channel_iter.next() {
+//!     assert_eq!(Some(false), explicit.is_explicit());
+//! } else {
+//!     panic!();
+//! }
+//!
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ItemElem::Itunes(itunes::Elem::Duration(duration))) = item_iter.next() {
+//!     assert_eq!(Some(3723), duration.seconds());
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [itunes]: https://help.apple.com/itc/podcasts_connect/#/itcb54353390
+
+use maybe_xml::{
+    token::{
+        self,
+        prop::{AttributeValue, Attributes, TagName},
+        Token,
+    },
+    Reader,
+};
+
+use crate::{xml, Tag};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+macro_rules! impl_attr {
+    ($x:ident, $fn_name:ident, $name:literal) => {
+        impl<'a> $x<'a> {
+            #[inline]
+            #[must_use]
+            pub fn $fn_name(&self) -> Option<AttributeValue<'a>> {
+                self.tag.find_attribute($name)
+            }
+        }
+    };
+}
+
+macro_rules! impl_iter {
+    (with_tag $iter_name:ident, $elem_ty:ident, $fn_name:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $iter_name<'a> {
+            tag: Tag<'a>,
+            reader: Reader<'a>,
+            pos: usize,
+        }
+
+        impl<'a> Iterator for $iter_name<'a> {
+            type Item = $elem_ty<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while let Some(token) = self.reader.tokenize(&mut self.pos) {
+                    match token.ty() {
+                        token::Ty::StartTag(tag) => {
+                            let tag_name = tag.name();
+
+                            let content = xml::collect_bytes_until_end_tag(
+                                tag_name,
+                                &self.reader,
+                                &mut self.pos,
+                            );
+
+                            return Some($fn_name(Tag::Start(tag), tag_name, content));
+                        }
+                        token::Ty::EmptyElementTag(tag) => {
+                            let tag_name = tag.name();
+
+                            return Some($fn_name(Tag::EmptyElement(tag), tag_name, ""));
+                        }
+                        token::Ty::Characters(content) => {
+                            if content.content().as_str().trim().is_empty() {
+                                continue;
+                            }
+                        }
+                        token::Ty::EndTag(_)
+                        | token::Ty::ProcessingInstruction(_)
+                        | token::Ty::Declaration(_)
+                        | token::Ty::Comment(_)
+                        | token::Ty::Cdata(_) => {
+                            // skip
+                        }
+                    }
+
+                    return Some($elem_ty::Raw(token));
+                }
+
+                None
+            }
+        }
+
+        impl<'a> $iter_name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(
+    Author,
+    Summary,
+    Subtitle,
+    Title,
+    Keywords,
+    Image,
+    Explicit,
+    Duration,
+    Episode,
+    Season,
+    EpisodeType,
+    Ty,
+    Block,
+    Complete,
+    NewFeedUrl,
+);
+
+impl_attr!(Image, href, "href");
+
+#[must_use]
+fn is_yes(value: &str) -> bool {
+    value.trim().eq_ignore_ascii_case("yes")
+}
+
+impl<'a> Explicit<'a> {
+    /// Normalizes the explicit flag.
+    ///
+    /// `yes`, `true`, and `explicit` are explicit while `no`, `false`, and
+    /// `clean` are not. Returns `None` for any other value.
+    #[must_use]
+    pub fn is_explicit(&self) -> Option<bool> {
+        let value = self.content.trim();
+        if ["yes", "true", "explicit"]
+            .iter()
+            .any(|v| value.eq_ignore_ascii_case(v))
+        {
+            Some(true)
+        } else if ["no", "false", "clean"]
+            .iter()
+            .any(|v| value.eq_ignore_ascii_case(v))
+        {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Duration<'a> {
+    /// Normalizes the duration to a number of seconds.
+    ///
+    /// The duration may be in the form of `HH:MM:SS`, `MM:SS`, or a number of
+    /// seconds. Fractional seconds are truncated.
+    #[must_use]
+    pub fn seconds(&self) -> Option<u64> {
+        let value = self.content.trim();
+        let value = value.split_once('.').map_or(value, |(secs, _)| secs);
+        if value.is_empty() {
+            return None;
+        }
+
+        let mut total: u64 = 0;
+        let mut count = 0;
+        for part in value.split(':') {
+            count += 1;
+            if count > 3 || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let part = part.parse::<u64>().ok()?;
+            total = total.checked_mul(60)?.checked_add(part)?;
+        }
+
+        Some(total)
+    }
+}
+
+impl<'a> Episode<'a> {
+    #[must_use]
+    pub fn number(&self) -> Option<u64> {
+        self.content.trim().parse().ok()
+    }
+}
+
+impl<'a> Season<'a> {
+    #[must_use]
+    pub fn number(&self) -> Option<u64> {
+        self.content.trim().parse().ok()
+    }
+}
+
+/// The type of an episode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EpisodeTypeValue {
+    Full,
+    Trailer,
+    Bonus,
+}
+
+impl<'a> EpisodeType<'a> {
+    #[must_use]
+    pub fn value(&self) -> Option<EpisodeTypeValue> {
+        let value = self.content.trim();
+        if value.eq_ignore_ascii_case("full") {
+            Some(EpisodeTypeValue::Full)
+        } else if value.eq_ignore_ascii_case("trailer") {
+            Some(EpisodeTypeValue::Trailer)
+        } else if value.eq_ignore_ascii_case("bonus") {
+            Some(EpisodeTypeValue::Bonus)
+        } else {
+            None
+        }
+    }
+}
+
+/// The type of a show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShowTy {
+    Episodic,
+    Serial,
+}
+
+impl<'a> Ty<'a> {
+    #[must_use]
+    pub fn value(&self) -> Option<ShowTy> {
+        let value = self.content.trim();
+        if value.eq_ignore_ascii_case("episodic") {
+            Some(ShowTy::Episodic)
+        } else if value.eq_ignore_ascii_case("serial") {
+            Some(ShowTy::Serial)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Block<'a> {
+    /// Returns true if the content is `Yes`.
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        is_yes(self.content)
+    }
+}
+
+impl<'a> Complete<'a> {
+    /// Returns true if the content is `Yes`.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        is_yes(self.content)
+    }
+}
+
+content_elem!(OwnerName, OwnerEmail);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OwnerElem<'a> {
+    Name(OwnerName<'a>),
+    Email(OwnerEmail<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CategoryElem<'a> {
+    Category(CategoryIter<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    Author(Author<'a>),
+    Summary(Summary<'a>),
+    Subtitle(Subtitle<'a>),
+    Title(Title<'a>),
+    Keywords(Keywords<'a>),
+    Image(Image<'a>),
+    Explicit(Explicit<'a>),
+    Duration(Duration<'a>),
+    Episode(Episode<'a>),
+    Season(Season<'a>),
+    EpisodeType(EpisodeType<'a>),
+    Ty(Ty<'a>),
+    Block(Block<'a>),
+    Complete(Complete<'a>),
+    NewFeedUrl(NewFeedUrl<'a>),
+    Owner(OwnerIter<'a>),
+    Category(CategoryIter<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> OwnerElem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> OwnerElem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("name", OwnerName, OwnerElem::Name);
+        return_content!("email", OwnerEmail, OwnerElem::Email);
+
+        OwnerElem::Unknown(Unknown { tag, content })
+    }
+}
+
+impl<'a> CategoryElem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> CategoryElem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_iter {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty {
+                        tag,
+                        reader: Reader::from_str(content),
+                        pos: 0,
+                    });
+                }
+            };
+        }
+
+        return_iter!("category", CategoryIter, CategoryElem::Category);
+
+        CategoryElem::Unknown(Unknown { tag, content })
+    }
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        macro_rules! return_iter {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty {
+                        tag,
+                        reader: Reader::from_str(content),
+                        pos: 0,
+                    });
+                }
+            };
+        }
+
+        return_content!("author", Author, Elem::Author);
+        return_content!("summary", Summary, Elem::Summary);
+        return_content!("subtitle", Subtitle, Elem::Subtitle);
+        return_content!("title", Title, Elem::Title);
+        return_content!("keywords", Keywords, Elem::Keywords);
+        return_content!("image", Image, Elem::Image);
+        return_content!("explicit", Explicit, Elem::Explicit);
+        return_content!("duration", Duration, Elem::Duration);
+        return_content!("episode", Episode, Elem::Episode);
+        return_content!("season", Season, Elem::Season);
+        return_content!("episodeType", EpisodeType, Elem::EpisodeType);
+        return_content!("type", Ty, Elem::Ty);
+        return_content!("block", Block, Elem::Block);
+        return_content!("complete", Complete, Elem::Complete);
+        return_content!("new-feed-url", NewFeedUrl, Elem::NewFeedUrl);
+
+        return_iter!("owner", OwnerIter, Elem::Owner);
+        return_iter!("category", CategoryIter, Elem::Category);
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+#[must_use]
+pub(crate) fn is_itunes(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "itunes")
+}
+
+impl_iter!(with_tag OwnerIter, OwnerElem, OwnerElem::new);
+
+impl_iter!(with_tag CategoryIter, CategoryElem, CategoryElem::new);
+impl_attr!(CategoryIter, text, "text");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rss::{self, ChannelElem, ItemElem, RssElem};
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn eval_itunes_channel_and_item() {
+        let input = r#"
+<rss xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+    <channel>
+        <title>Podcast Title</title>
+        <itunes:author>Jane Doe</itunes:author>
+        <itunes:image href="https://example.com/cover.jpg"/>
+        <itunes:explicit>yes</itunes:explicit>
+        <itunes:type>serial</itunes:type>
+        <itunes:owner>
+            <itunes:name>Jane Doe</itunes:name>
+            <itunes:email>jane@example.com</itunes:email>
+        </itunes:owner>
+        <itunes:category text="Society &amp; Culture">
+            <itunes:category text="Documentary"/>
+        </itunes:category>
+        <itunes:complete>Yes</itunes:complete>
+        <itunes:new-feed-url>https://example.com/new.xml</itunes:new-feed-url>
+        <item>
+            <title>Episode Title</title>
+            <itunes:duration>12:34</itunes:duration>
+            <itunes:episode>3</itunes:episode>
+            <itunes:season>2</itunes:season>
+            <itunes:episodeType>trailer</itunes:episodeType>
+            <itunes:block>No</itunes:block>
+            <itunes:explicit>true</itunes:explicit>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        assert!(matches!(channel_iter.next(), Some(ChannelElem::Title(_))));
+
+        if let Some(ChannelElem::Itunes(Elem::Author(author))) = channel_iter.next() {
+            assert_eq!("Jane Doe", author.content());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::Image(image))) = channel_iter.next() {
+            assert_eq!(
+                Some("https://example.com/cover.jpg"),
+                image.href().map(|v| v.as_str())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::Explicit(explicit))) = channel_iter.next() {
+            assert_eq!(Some(true), explicit.is_explicit());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::Ty(ty))) = channel_iter.next() {
+            assert_eq!(Some(ShowTy::Serial), ty.value());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::Owner(mut owner_iter))) = channel_iter.next() {
+            if let Some(OwnerElem::Name(name)) = owner_iter.next() {
+                assert_eq!("Jane Doe", name.content());
+            } else {
+                panic!();
+            }
+            if let Some(OwnerElem::Email(email)) = owner_iter.next() {
+                assert_eq!("jane@example.com", email.content());
+            } else {
+                panic!();
+            }
+            assert_eq!(None, owner_iter.next());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::Category(mut category_iter))) = channel_iter.next() {
+            assert_eq!(
+                Some("Society &amp; Culture"),
+                category_iter.text().map(|v| v.as_str())
+            );
+            if let Some(CategoryElem::Category(mut subcategory_iter)) = category_iter.next() {
+                assert_eq!(
+                    Some("Documentary"),
+                    subcategory_iter.text().map(|v| v.as_str())
+                );
+                assert_eq!(None, subcategory_iter.next());
+            } else {
+                panic!();
+            }
+            assert_eq!(None, category_iter.next());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::Complete(complete))) = channel_iter.next() {
+            assert!(complete.is_complete());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Itunes(Elem::NewFeedUrl(url))) = channel_iter.next() {
+            assert_eq!("https://example.com/new.xml", url.content());
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+
+        assert!(matches!(item_iter.next(), Some(ItemElem::Title(_))));
+
+        if let Some(ItemElem::Itunes(Elem::Duration(duration))) = item_iter.next() {
+            assert_eq!(Some(754), duration.seconds());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Itunes(Elem::Episode(episode))) = item_iter.next() {
+            assert_eq!(Some(3), episode.number());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Itunes(Elem::Season(season))) = item_iter.next() {
+            assert_eq!(Some(2), season.number());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Itunes(Elem::EpisodeType(episode_ty))) = item_iter.next() {
+            assert_eq!(Some(EpisodeTypeValue::Trailer), episode_ty.value());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Itunes(Elem::Block(block))) = item_iter.next() {
+            assert!(!block.is_blocked());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Itunes(Elem::Explicit(explicit))) = item_iter.next() {
+            assert_eq!(Some(true), explicit.is_explicit());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, item_iter.next());
+        assert_eq!(None, channel_iter.next());
+    }
+
+    #[test]
+    fn duration_seconds() {
+        let tag = Tag::Start(match Reader::from_str("<d>").tokenize(&mut 0) {
+            Some(token) => match token.ty() {
+                token::Ty::StartTag(tag) => tag,
+                _ => panic!(),
+            },
+            None => panic!(),
+        });
+
+        for (content, expected) in [
+            ("3723", Some(3723)),
+            (" 1:02:03 ", Some(3723)),
+            ("62:03", Some(3723)),
+            ("0:00:10.5", Some(10)),
+            ("", None),
+            ("1:2:3:4", None),
+            ("1::3", None),
+            ("abc", None),
+            ("-5", None),
+        ] {
+            assert_eq!(expected, Duration { tag, content }.seconds(), "{content}");
+        }
+    }
+}
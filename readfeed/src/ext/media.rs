@@ -0,0 +1,483 @@
+//! [Media RSS][mrss] is an extension for describing media such as videos,
+//! images, and audio in RSS items and Atom entries.
+//!
+//! Media elements are returned as [`rss::ItemElem::Media`][crate::rss::ItemElem::Media]
+//! and [`atom::EntryElem::Media`][crate::atom::EntryElem::Media].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::ext::media;
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:media="http://search.yahoo.com/mrss/">
+//!     <channel>
+//!         <item>
+//!             <title>Item Title</title>
+//!             <media:group>
+//!                 <media:title>Video Title</media:title>
+//!                 <media:content url="https://example.com/1.mp4" type="video/mp4" width="640" height="480"/>
+//!                 <media:thumbnail url="https://example.com/1.jpg" width="120" height="90"/>
+//!             </media:group>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ItemElem::Title(title)) = item_iter.next() {
+//!     assert_eq!("Item Title", title.content());
+//! } else {
+//!     panic!();
+//! }
+//!
+//! let Some(ItemElem::Media(media::Elem::Group(mut group_iter))) = item_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(media::Elem::Title(title)) = group_iter.next() {
+//!     assert_eq!("Video Title", title.content());
+//! } else {
+//!     panic!();
+//! }
+//! if let Some(media::Elem::Content(content)) = group_iter.next() {
+//!     assert_eq!(Some("https://example.com/1.mp4"), content.url().map(|v| v.as_str()));
+//!     assert_eq!(Some("video/mp4"), content.ty().map(|v| v.as_str()));
+//!     assert_eq!(Some("640"), content.width().map(|v| v.as_str()));
+//! } else {
+//!     panic!();
+//! }
+//! if let Some(media::Elem::Thumbnail(thumbnail)) = group_iter.next() {
+//!     assert_eq!(Some("https://example.com/1.jpg"), thumbnail.url().map(|v| v.as_str()));
+//! } else {
+//!     panic!();
+//! }
+//! assert_eq!(None, group_iter.next());
+//! assert_eq!(None, item_iter.next());
+//! ```
+//!
+//! [mrss]: https://www.rssboard.org/media-rss
+
+use maybe_xml::{
+    token::{
+        self,
+        prop::{AttributeValue, Attributes, TagName},
+        Token,
+    },
+    Reader,
+};
+
+use crate::{xml, Tag};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+macro_rules! impl_attr {
+    ($x:ident, $fn_name:ident, $name:literal) => {
+        impl<'a> $x<'a> {
+            #[inline]
+            #[must_use]
+            pub fn $fn_name(&self) -> Option<AttributeValue<'a>> {
+                self.tag.find_attribute($name)
+            }
+        }
+    };
+}
+
+macro_rules! impl_iter {
+    (with_tag $iter_name:ident, $elem_ty:ident, $fn_name:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $iter_name<'a> {
+            tag: Tag<'a>,
+            reader: Reader<'a>,
+            pos: usize,
+        }
+
+        impl<'a> Iterator for $iter_name<'a> {
+            type Item = $elem_ty<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while let Some(token) = self.reader.tokenize(&mut self.pos) {
+                    match token.ty() {
+                        token::Ty::StartTag(tag) => {
+                            let tag_name = tag.name();
+
+                            let content = xml::collect_bytes_until_end_tag(
+                                tag_name,
+                                &self.reader,
+                                &mut self.pos,
+                            );
+
+                            return Some($fn_name(Tag::Start(tag), tag_name, content));
+                        }
+                        token::Ty::EmptyElementTag(tag) => {
+                            let tag_name = tag.name();
+
+                            return Some($fn_name(Tag::EmptyElement(tag), tag_name, ""));
+                        }
+                        token::Ty::Characters(content) => {
+                            if content.content().as_str().trim().is_empty() {
+                                continue;
+                            }
+                        }
+                        token::Ty::EndTag(_)
+                        | token::Ty::ProcessingInstruction(_)
+                        | token::Ty::Declaration(_)
+                        | token::Ty::Comment(_)
+                        | token::Ty::Cdata(_) => {
+                            // skip
+                        }
+                    }
+
+                    return Some($elem_ty::Raw(token));
+                }
+
+                None
+            }
+        }
+
+        impl<'a> $iter_name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(Thumbnail);
+impl_attr!(Thumbnail, url, "url");
+impl_attr!(Thumbnail, width, "width");
+impl_attr!(Thumbnail, height, "height");
+impl_attr!(Thumbnail, time, "time");
+
+content_elem!(Title, Description);
+impl_attr!(Title, ty, "type");
+impl_attr!(Description, ty, "type");
+
+content_elem!(Player);
+impl_attr!(Player, url, "url");
+impl_attr!(Player, width, "width");
+impl_attr!(Player, height, "height");
+
+content_elem!(Credit);
+impl_attr!(Credit, role, "role");
+impl_attr!(Credit, scheme, "scheme");
+
+content_elem!(Rating);
+impl_attr!(Rating, scheme, "scheme");
+
+content_elem!(Keywords);
+
+content_elem!(StarRating);
+impl_attr!(StarRating, average, "average");
+impl_attr!(StarRating, count, "count");
+impl_attr!(StarRating, min, "min");
+impl_attr!(StarRating, max, "max");
+
+content_elem!(Statistics);
+impl_attr!(Statistics, views, "views");
+impl_attr!(Statistics, favorites, "favorites");
+
+content_elem!(Tags);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommunityElem<'a> {
+    StarRating(StarRating<'a>),
+    Statistics(Statistics<'a>),
+    Tags(Tags<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    Group(GroupIter<'a>),
+    Content(ContentIter<'a>),
+    Thumbnail(Thumbnail<'a>),
+    Title(Title<'a>),
+    Description(Description<'a>),
+    Player(Player<'a>),
+    Credit(Credit<'a>),
+    Rating(Rating<'a>),
+    Community(CommunityIter<'a>),
+    Keywords(Keywords<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> CommunityElem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> CommunityElem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("starRating", StarRating, CommunityElem::StarRating);
+        return_content!("statistics", Statistics, CommunityElem::Statistics);
+        return_content!("tags", Tags, CommunityElem::Tags);
+
+        CommunityElem::Unknown(Unknown { tag, content })
+    }
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        macro_rules! return_iter {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty {
+                        tag,
+                        reader: Reader::from_str(content),
+                        pos: 0,
+                    });
+                }
+            };
+        }
+
+        return_iter!("group", GroupIter, Elem::Group);
+        return_iter!("content", ContentIter, Elem::Content);
+
+        return_content!("thumbnail", Thumbnail, Elem::Thumbnail);
+        return_content!("title", Title, Elem::Title);
+        return_content!("description", Description, Elem::Description);
+        return_content!("player", Player, Elem::Player);
+        return_content!("credit", Credit, Elem::Credit);
+        return_content!("rating", Rating, Elem::Rating);
+
+        return_iter!("community", CommunityIter, Elem::Community);
+
+        return_content!("keywords", Keywords, Elem::Keywords);
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+/// Returns true if the tag name uses the `media` namespace prefix.
+#[must_use]
+pub(crate) fn is_media(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "media")
+}
+
+impl_iter!(with_tag GroupIter, Elem, Elem::new);
+
+impl_iter!(with_tag ContentIter, Elem, Elem::new);
+impl_attr!(ContentIter, url, "url");
+impl_attr!(ContentIter, file_size, "fileSize");
+impl_attr!(ContentIter, ty, "type");
+impl_attr!(ContentIter, medium, "medium");
+impl_attr!(ContentIter, is_default, "isDefault");
+impl_attr!(ContentIter, expression, "expression");
+impl_attr!(ContentIter, bitrate, "bitrate");
+impl_attr!(ContentIter, framerate, "framerate");
+impl_attr!(ContentIter, sampling_rate, "samplingrate");
+impl_attr!(ContentIter, channels, "channels");
+impl_attr!(ContentIter, duration, "duration");
+impl_attr!(ContentIter, width, "width");
+impl_attr!(ContentIter, height, "height");
+impl_attr!(ContentIter, lang, "lang");
+
+impl_iter!(with_tag CommunityIter, CommunityElem, CommunityElem::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::atom::{self, EntryElem, FeedElem};
+
+    #[test]
+    fn eval_media_in_atom_entry() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/">
+    <entry>
+        <title>Entry Title</title>
+        <media:group>
+            <media:title>Video Title</media:title>
+            <media:content url="https://example.com/1.mp4" type="video/mp4" medium="video" width="640" height="480" duration="185" fileSize="1024" bitrate="128"/>
+            <media:thumbnail url="https://example.com/1.jpg" width="120" height="90"/>
+            <media:description>Video Description</media:description>
+            <media:player url="https://example.com/player/1" width="640" height="480"/>
+            <media:credit role="author" scheme="urn:ebu">Jane Doe</media:credit>
+            <media:rating scheme="urn:simple">nonadult</media:rating>
+            <media:community>
+                <media:starRating average="4.5" count="10" min="1" max="5"/>
+                <media:statistics views="100"/>
+            </media:community>
+            <media:keywords>lorem, ipsum</media:keywords>
+        </media:group>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = atom::Iter::new(input);
+
+        let Some(atom::Elem::Feed(mut feed_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+            panic!();
+        };
+
+        if let Some(EntryElem::Title(title)) = entry_iter.next() {
+            assert_eq!("Entry Title", title.content());
+        } else {
+            panic!();
+        }
+
+        let Some(EntryElem::Media(Elem::Group(mut group_iter))) = entry_iter.next() else {
+            panic!();
+        };
+
+        if let Some(Elem::Title(title)) = group_iter.next() {
+            assert_eq!("Video Title", title.content());
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Content(mut content)) = group_iter.next() {
+            assert_eq!(
+                Some("https://example.com/1.mp4"),
+                content.url().map(|v| v.as_str())
+            );
+            assert_eq!(Some("video/mp4"), content.ty().map(|v| v.as_str()));
+            assert_eq!(Some("video"), content.medium().map(|v| v.as_str()));
+            assert_eq!(Some("640"), content.width().map(|v| v.as_str()));
+            assert_eq!(Some("480"), content.height().map(|v| v.as_str()));
+            assert_eq!(Some("185"), content.duration().map(|v| v.as_str()));
+            assert_eq!(Some("1024"), content.file_size().map(|v| v.as_str()));
+            assert_eq!(Some("128"), content.bitrate().map(|v| v.as_str()));
+            assert_eq!(None, content.next());
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Thumbnail(thumbnail)) = group_iter.next() {
+            assert_eq!(
+                Some("https://example.com/1.jpg"),
+                thumbnail.url().map(|v| v.as_str())
+            );
+            assert_eq!(Some("120"), thumbnail.width().map(|v| v.as_str()));
+            assert_eq!(Some("90"), thumbnail.height().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Description(desc)) = group_iter.next() {
+            assert_eq!("Video Description", desc.content());
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Player(player)) = group_iter.next() {
+            assert_eq!(
+                Some("https://example.com/player/1"),
+                player.url().map(|v| v.as_str())
+            );
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Credit(credit)) = group_iter.next() {
+            assert_eq!("Jane Doe", credit.content());
+            assert_eq!(Some("author"), credit.role().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Rating(rating)) = group_iter.next() {
+            assert_eq!("nonadult", rating.content());
+            assert_eq!(Some("urn:simple"), rating.scheme().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Community(mut community_iter)) = group_iter.next() {
+            if let Some(CommunityElem::StarRating(star_rating)) = community_iter.next() {
+                assert_eq!(Some("4.5"), star_rating.average().map(|v| v.as_str()));
+                assert_eq!(Some("10"), star_rating.count().map(|v| v.as_str()));
+            } else {
+                panic!();
+            }
+            if let Some(CommunityElem::Statistics(statistics)) = community_iter.next() {
+                assert_eq!(Some("100"), statistics.views().map(|v| v.as_str()));
+            } else {
+                panic!();
+            }
+            assert_eq!(None, community_iter.next());
+        } else {
+            panic!();
+        }
+
+        if let Some(Elem::Keywords(keywords)) = group_iter.next() {
+            assert_eq!("lorem, ipsum", keywords.content());
+        } else {
+            panic!();
+        }
+
+        assert_eq!(None, group_iter.next());
+        assert_eq!(None, entry_iter.next());
+    }
+}
@@ -0,0 +1,658 @@
+//! [Podcasting 2.0][podcast] elements from the Podcast Index namespace.
+//!
+//! Podcast elements are returned as
+//! [`rss::ChannelElem::Podcast`][crate::rss::ChannelElem::Podcast] and
+//! [`rss::ItemElem::Podcast`][crate::rss::ItemElem::Podcast].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::ext::podcast;
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:podcast="https://podcastindex.org/namespace/1.0">
+//!     <channel>
+//!         <podcast:locked owner="jane@example.com">yes</podcast:locked>
+//!         <item>
+//!             <podcast:transcript url="https://example.com/1.vtt" type="text/vtt"/>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ChannelElem::Podcast(podcast::Elem::Locked(locked))) = channel_iter.next() {
+//!     assert!(locked.is_locked());
+//!     assert_eq!(Some("jane@example.com"), locked.owner().map(|v| v.as_str()));
+//! } else {
+//!     panic!();
+//! }
+//!
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ItemElem::Podcast(podcast::Elem::Transcript(transcript))) = item_iter.next() {
+//!     assert_eq!(Some("https://example.com/1.vtt"), transcript.url().map(|v| v.as_str()));
+//!     assert_eq!(Some("text/vtt"), transcript.ty().map(|v| v.as_str()));
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [podcast]: https://podcastindex.org/namespace/1.0
+
+use maybe_xml::{
+    token::{
+        self,
+        prop::{AttributeValue, Attributes, TagName},
+        Token,
+    },
+    Reader,
+};
+
+use crate::{
+    rss::ItemElem,
+    xml::{self, Scope},
+    Tag,
+};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+macro_rules! impl_attr {
+    ($x:ident, $fn_name:ident, $name:literal) => {
+        impl<'a> $x<'a> {
+            #[inline]
+            #[must_use]
+            pub fn $fn_name(&self) -> Option<AttributeValue<'a>> {
+                self.tag.find_attribute($name)
+            }
+        }
+    };
+}
+
+macro_rules! impl_iter {
+    (with_tag $iter_name:ident, $elem_ty:ident, $fn_name:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $iter_name<'a> {
+            tag: Tag<'a>,
+            reader: Reader<'a>,
+            pos: usize,
+        }
+
+        impl<'a> Iterator for $iter_name<'a> {
+            type Item = $elem_ty<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while let Some(token) = self.reader.tokenize(&mut self.pos) {
+                    match token.ty() {
+                        token::Ty::StartTag(tag) => {
+                            let tag_name = tag.name();
+
+                            let content = xml::collect_bytes_until_end_tag(
+                                tag_name,
+                                &self.reader,
+                                &mut self.pos,
+                            );
+
+                            return Some($fn_name(Tag::Start(tag), tag_name, content));
+                        }
+                        token::Ty::EmptyElementTag(tag) => {
+                            let tag_name = tag.name();
+
+                            return Some($fn_name(Tag::EmptyElement(tag), tag_name, ""));
+                        }
+                        token::Ty::Characters(content) => {
+                            if content.content().as_str().trim().is_empty() {
+                                continue;
+                            }
+                        }
+                        token::Ty::EndTag(_)
+                        | token::Ty::ProcessingInstruction(_)
+                        | token::Ty::Declaration(_)
+                        | token::Ty::Comment(_)
+                        | token::Ty::Cdata(_) => {
+                            // skip
+                        }
+                    }
+
+                    return Some($elem_ty::Raw(token));
+                }
+
+                None
+            }
+        }
+
+        impl<'a> $iter_name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    (with_scope $iter_name:ident, $elem_ty:ident, $fn_name:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $iter_name<'a> {
+            tag: Tag<'a>,
+            reader: Reader<'a>,
+            pos: usize,
+            scope: Scope<'a>,
+        }
+
+        impl<'a> Iterator for $iter_name<'a> {
+            type Item = $elem_ty<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while let Some(token) = self.reader.tokenize(&mut self.pos) {
+                    match token.ty() {
+                        token::Ty::StartTag(tag) => {
+                            let tag_name = tag.name();
+
+                            let content = xml::collect_bytes_until_end_tag(
+                                tag_name,
+                                &self.reader,
+                                &mut self.pos,
+                            );
+
+                            return Some($fn_name(Tag::Start(tag), tag_name, content, self.scope));
+                        }
+                        token::Ty::EmptyElementTag(tag) => {
+                            let tag_name = tag.name();
+
+                            return Some($fn_name(
+                                Tag::EmptyElement(tag),
+                                tag_name,
+                                "",
+                                self.scope,
+                            ));
+                        }
+                        token::Ty::Characters(content) => {
+                            if content.content().as_str().trim().is_empty() {
+                                continue;
+                            }
+                        }
+                        token::Ty::EndTag(_)
+                        | token::Ty::ProcessingInstruction(_)
+                        | token::Ty::Declaration(_)
+                        | token::Ty::Comment(_)
+                        | token::Ty::Cdata(_) => {
+                            // skip
+                        }
+                    }
+
+                    return Some($elem_ty::Raw(token));
+                }
+
+                None
+            }
+        }
+
+        impl<'a> $iter_name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(Transcript);
+impl_attr!(Transcript, url, "url");
+impl_attr!(Transcript, ty, "type");
+impl_attr!(Transcript, language, "language");
+impl_attr!(Transcript, rel, "rel");
+
+content_elem!(Chapters);
+impl_attr!(Chapters, url, "url");
+impl_attr!(Chapters, ty, "type");
+
+content_elem!(Person);
+impl_attr!(Person, role, "role");
+impl_attr!(Person, group, "group");
+impl_attr!(Person, img, "img");
+impl_attr!(Person, href, "href");
+
+content_elem!(Funding);
+impl_attr!(Funding, url, "url");
+
+content_elem!(ValueRecipient);
+impl_attr!(ValueRecipient, name, "name");
+impl_attr!(ValueRecipient, custom_key, "customKey");
+impl_attr!(ValueRecipient, custom_value, "customValue");
+impl_attr!(ValueRecipient, ty, "type");
+impl_attr!(ValueRecipient, address, "address");
+impl_attr!(ValueRecipient, split, "split");
+impl_attr!(ValueRecipient, fee, "fee");
+
+impl<'a> ValueRecipient<'a> {
+    /// Returns true if the `fee` attribute is `true`.
+    #[must_use]
+    pub fn is_fee(&self) -> bool {
+        self.fee()
+            .map(|v| v.as_str().trim().eq_ignore_ascii_case("true"))
+            .unwrap_or_default()
+    }
+}
+
+content_elem!(Soundbite);
+impl_attr!(Soundbite, start_time, "startTime");
+impl_attr!(Soundbite, duration, "duration");
+
+content_elem!(Location);
+impl_attr!(Location, geo, "geo");
+impl_attr!(Location, osm, "osm");
+
+content_elem!(Season);
+impl_attr!(Season, name, "name");
+
+impl<'a> Season<'a> {
+    #[must_use]
+    pub fn number(&self) -> Option<u64> {
+        self.content.trim().parse().ok()
+    }
+}
+
+content_elem!(Episode);
+impl_attr!(Episode, display, "display");
+
+impl<'a> Episode<'a> {
+    /// The episode number which may be a decimal value.
+    #[must_use]
+    pub fn number(&self) -> Option<f64> {
+        self.content.trim().parse().ok()
+    }
+}
+
+content_elem!(Source);
+impl_attr!(Source, uri, "uri");
+impl_attr!(Source, content_ty, "contentType");
+
+content_elem!(Integrity);
+impl_attr!(Integrity, ty, "type");
+impl_attr!(Integrity, value, "value");
+
+content_elem!(Guid);
+
+content_elem!(Locked);
+impl_attr!(Locked, owner, "owner");
+
+impl<'a> Locked<'a> {
+    /// Returns true if the content is `yes`.
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.content.trim().eq_ignore_ascii_case("yes")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValueElem<'a> {
+    ValueRecipient(ValueRecipient<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlternateEnclosureElem<'a> {
+    Source(Source<'a>),
+    Integrity(Integrity<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    Transcript(Transcript<'a>),
+    Chapters(Chapters<'a>),
+    Person(Person<'a>),
+    Funding(Funding<'a>),
+    Value(ValueIter<'a>),
+    Soundbite(Soundbite<'a>),
+    Location(Location<'a>),
+    Season(Season<'a>),
+    Episode(Episode<'a>),
+    AlternateEnclosure(AlternateEnclosureIter<'a>),
+    Guid(Guid<'a>),
+    Locked(Locked<'a>),
+    LiveItem(LiveItemIter<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> ValueElem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> ValueElem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("valueRecipient", ValueRecipient, ValueElem::ValueRecipient);
+
+        ValueElem::Unknown(Unknown { tag, content })
+    }
+}
+
+impl<'a> AlternateEnclosureElem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> AlternateEnclosureElem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("source", Source, AlternateEnclosureElem::Source);
+        return_content!("integrity", Integrity, AlternateEnclosureElem::Integrity);
+
+        AlternateEnclosureElem::Unknown(Unknown { tag, content })
+    }
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        macro_rules! return_iter {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty {
+                        tag,
+                        reader: Reader::from_str(content),
+                        pos: 0,
+                    });
+                }
+            };
+        }
+
+        return_content!("transcript", Transcript, Elem::Transcript);
+        return_content!("chapters", Chapters, Elem::Chapters);
+        return_content!("person", Person, Elem::Person);
+        return_content!("funding", Funding, Elem::Funding);
+
+        return_iter!("value", ValueIter, Elem::Value);
+
+        return_content!("soundbite", Soundbite, Elem::Soundbite);
+        return_content!("location", Location, Elem::Location);
+        return_content!("season", Season, Elem::Season);
+        return_content!("episode", Episode, Elem::Episode);
+
+        return_iter!(
+            "alternateEnclosure",
+            AlternateEnclosureIter,
+            Elem::AlternateEnclosure
+        );
+
+        return_content!("guid", Guid, Elem::Guid);
+        return_content!("locked", Locked, Elem::Locked);
+
+        if local_name.eq_ignore_ascii_case("liveItem") {
+            return Elem::LiveItem(LiveItemIter {
+                tag,
+                reader: Reader::from_str(content),
+                pos: 0,
+                scope: scope.enter(tag),
+            });
+        }
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+#[must_use]
+pub(crate) fn is_podcast(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "podcast")
+}
+
+impl_iter!(with_tag ValueIter, ValueElem, ValueElem::new);
+impl_attr!(ValueIter, ty, "type");
+impl_attr!(ValueIter, method, "method");
+impl_attr!(ValueIter, suggested, "suggested");
+
+impl_iter!(with_tag AlternateEnclosureIter, AlternateEnclosureElem, AlternateEnclosureElem::new);
+impl_attr!(AlternateEnclosureIter, ty, "type");
+impl_attr!(AlternateEnclosureIter, length, "length");
+impl_attr!(AlternateEnclosureIter, bitrate, "bitrate");
+impl_attr!(AlternateEnclosureIter, height, "height");
+impl_attr!(AlternateEnclosureIter, lang, "lang");
+impl_attr!(AlternateEnclosureIter, title, "title");
+impl_attr!(AlternateEnclosureIter, rel, "rel");
+impl_attr!(AlternateEnclosureIter, codecs, "codecs");
+impl_attr!(AlternateEnclosureIter, default, "default");
+
+impl_iter!(with_scope LiveItemIter, ItemElem, ItemElem::new);
+impl_attr!(LiveItemIter, status, "status");
+impl_attr!(LiveItemIter, start, "start");
+impl_attr!(LiveItemIter, end, "end");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rss::{self, ChannelElem, RssElem};
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn eval_podcast_channel_and_item() {
+        let input = r#"
+<rss xmlns:podcast="https://podcastindex.org/namespace/1.0">
+    <channel>
+        <podcast:guid>917393e3-1b1e-5cef-ace4-edaa54e1f810</podcast:guid>
+        <podcast:funding url="https://example.com/donate">Support the show!</podcast:funding>
+        <podcast:value type="lightning" method="keysend" suggested="0.00000005000">
+            <podcast:valueRecipient name="Host" type="node" address="02d5c1bf" split="90"/>
+            <podcast:valueRecipient name="App" type="node" address="03ae9f91" split="10" fee="true"/>
+        </podcast:value>
+        <podcast:liveItem status="live" start="2021-09-26T07:30:00.000-0600" end="2021-09-26T09:30:00.000-0600">
+            <title>Live Episode</title>
+        </podcast:liveItem>
+        <item>
+            <podcast:chapters url="https://example.com/chapters.json" type="application/json+chapters"/>
+            <podcast:person role="guest" href="https://example.com/guest" img="https://example.com/guest.jpg">John Doe</podcast:person>
+            <podcast:soundbite startTime="73.0" duration="60.0">Clip</podcast:soundbite>
+            <podcast:location geo="geo:30.2672,97.7431" osm="R113314">Austin, TX</podcast:location>
+            <podcast:season name="Podcasting 2.0">1</podcast:season>
+            <podcast:episode display="Ch.3">315.5</podcast:episode>
+            <podcast:alternateEnclosure type="audio/opus" length="32400000" bitrate="96000" title="High quality" default="true">
+                <podcast:source uri="https://example.com/1.opus"/>
+                <podcast:integrity type="sri" value="sha384-ExVqijgYHm15PqQqdXfW95x+Rs6C+d6E/ICxyQOeFevnxNLR/wtJNrNYTjIysUBo"/>
+            </podcast:alternateEnclosure>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ChannelElem::Podcast(Elem::Guid(guid))) = channel_iter.next() {
+            assert_eq!("917393e3-1b1e-5cef-ace4-edaa54e1f810", guid.content());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Podcast(Elem::Funding(funding))) = channel_iter.next() {
+            assert_eq!("Support the show!", funding.content());
+            assert_eq!(
+                Some("https://example.com/donate"),
+                funding.url().map(|v| v.as_str())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Podcast(Elem::Value(mut value_iter))) = channel_iter.next() {
+            assert_eq!(Some("lightning"), value_iter.ty().map(|v| v.as_str()));
+            assert_eq!(Some("keysend"), value_iter.method().map(|v| v.as_str()));
+            if let Some(ValueElem::ValueRecipient(recipient)) = value_iter.next() {
+                assert_eq!(Some("Host"), recipient.name().map(|v| v.as_str()));
+                assert_eq!(Some("90"), recipient.split().map(|v| v.as_str()));
+                assert!(!recipient.is_fee());
+            } else {
+                panic!();
+            }
+            if let Some(ValueElem::ValueRecipient(recipient)) = value_iter.next() {
+                assert_eq!(Some("03ae9f91"), recipient.address().map(|v| v.as_str()));
+                assert!(recipient.is_fee());
+            } else {
+                panic!();
+            }
+            assert_eq!(None, value_iter.next());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Podcast(Elem::LiveItem(mut live_item_iter))) = channel_iter.next()
+        {
+            assert_eq!(Some("live"), live_item_iter.status().map(|v| v.as_str()));
+            if let Some(ItemElem::Title(title)) = live_item_iter.next() {
+                assert_eq!("Live Episode", title.content());
+            } else {
+                panic!();
+            }
+            assert_eq!(None, live_item_iter.next());
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ItemElem::Podcast(Elem::Chapters(chapters))) = item_iter.next() {
+            assert_eq!(
+                Some("https://example.com/chapters.json"),
+                chapters.url().map(|v| v.as_str())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Podcast(Elem::Person(person))) = item_iter.next() {
+            assert_eq!("John Doe", person.content());
+            assert_eq!(Some("guest"), person.role().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Podcast(Elem::Soundbite(soundbite))) = item_iter.next() {
+            assert_eq!(Some("73.0"), soundbite.start_time().map(|v| v.as_str()));
+            assert_eq!(Some("60.0"), soundbite.duration().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Podcast(Elem::Location(location))) = item_iter.next() {
+            assert_eq!("Austin, TX", location.content());
+            assert_eq!(Some("R113314"), location.osm().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Podcast(Elem::Season(season))) = item_iter.next() {
+            assert_eq!(Some(1), season.number());
+            assert_eq!(Some("Podcasting 2.0"), season.name().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Podcast(Elem::Episode(episode))) = item_iter.next() {
+            assert_eq!(Some(315.5), episode.number());
+            assert_eq!(Some("Ch.3"), episode.display().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Podcast(Elem::AlternateEnclosure(mut enclosure_iter))) =
+            item_iter.next()
+        {
+            assert_eq!(Some("audio/opus"), enclosure_iter.ty().map(|v| v.as_str()));
+            assert_eq!(
+                Some("32400000"),
+                enclosure_iter.length().map(|v| v.as_str())
+            );
+            assert_eq!(Some("true"), enclosure_iter.default().map(|v| v.as_str()));
+            if let Some(AlternateEnclosureElem::Source(source)) = enclosure_iter.next() {
+                assert_eq!(
+                    Some("https://example.com/1.opus"),
+                    source.uri().map(|v| v.as_str())
+                );
+            } else {
+                panic!();
+            }
+            if let Some(AlternateEnclosureElem::Integrity(integrity)) = enclosure_iter.next() {
+                assert_eq!(Some("sri"), integrity.ty().map(|v| v.as_str()));
+            } else {
+                panic!();
+            }
+            assert_eq!(None, enclosure_iter.next());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, item_iter.next());
+        assert_eq!(None, channel_iter.next());
+    }
+}
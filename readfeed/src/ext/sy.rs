@@ -0,0 +1,275 @@
+//! The [RSS syndication module][sy] describes how often a feed is updated.
+//!
+//! Syndication elements are returned as
+//! [`rss::ChannelElem::Sy`][crate::rss::ChannelElem::Sy].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::ext::sy::{self, Period};
+//! use readfeed::rss::{self, ChannelElem, Elem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+//!     <channel>
+//!         <sy:updatePeriod>hourly</sy:updatePeriod>
+//!         <sy:updateFrequency>2</sy:updateFrequency>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ChannelElem::Sy(sy::Elem::UpdatePeriod(period))) = channel_iter.next() {
+//!     assert_eq!(Some(Period::Hourly), period.value());
+//! } else {
+//!     panic!();
+//! }
+//! if let Some(ChannelElem::Sy(sy::Elem::UpdateFrequency(frequency))) = channel_iter.next() {
+//!     assert_eq!(Ok(2), frequency.value());
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [sy]: https://web.resource.org/rss/1.0/modules/syndication/
+
+use maybe_xml::token::{
+    prop::{Attributes, TagName},
+    Token,
+};
+
+use crate::{
+    date::DateTime,
+    value::{self, ParseError},
+    Tag,
+};
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(UpdatePeriod, UpdateFrequency, UpdateBase);
+
+/// The period over which a feed is updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Period {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Period {
+    /// The length of the period in seconds.
+    ///
+    /// Months are 30 days and years are 365 days.
+    #[must_use]
+    pub const fn seconds(self) -> u64 {
+        match self {
+            Period::Hourly => 3600,
+            Period::Daily => 86_400,
+            Period::Weekly => 7 * 86_400,
+            Period::Monthly => 30 * 86_400,
+            Period::Yearly => 365 * 86_400,
+        }
+    }
+}
+
+impl<'a> UpdatePeriod<'a> {
+    /// Parses the content as a period name.
+    ///
+    /// Names are matched ignoring case. Returns `None` for other names.
+    #[must_use]
+    pub fn value(&self) -> Option<Period> {
+        let value = self.content.trim();
+        [
+            ("hourly", Period::Hourly),
+            ("daily", Period::Daily),
+            ("weekly", Period::Weekly),
+            ("monthly", Period::Monthly),
+            ("yearly", Period::Yearly),
+        ]
+        .into_iter()
+        .find_map(|(name, period)| value.eq_ignore_ascii_case(name).then_some(period))
+    }
+}
+
+impl<'a> UpdateFrequency<'a> {
+    /// Parses the content as the number of updates per period.
+    #[inline]
+    pub fn value(&self) -> Result<u32, ParseError> {
+        value::parse_u32(self.content)
+    }
+}
+
+impl<'a> UpdateBase<'a> {
+    /// Parses the content as a W3C date and time.
+    #[inline]
+    #[must_use]
+    pub fn to_date_time(&self) -> Option<DateTime> {
+        DateTime::parse_w3cdtf(self.content)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    UpdatePeriod(UpdatePeriod<'a>),
+    UpdateFrequency(UpdateFrequency<'a>),
+    UpdateBase(UpdateBase<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("updatePeriod", UpdatePeriod, Elem::UpdatePeriod);
+        return_content!("updateFrequency", UpdateFrequency, Elem::UpdateFrequency);
+        return_content!("updateBase", UpdateBase, Elem::UpdateBase);
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+#[must_use]
+pub(crate) fn is_sy(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, "sy")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rss::{self, ChannelElem, RssElem};
+
+    #[test]
+    fn eval_sy_in_rss() {
+        let input = r#"
+<rss xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+    <channel>
+        <sy:updatePeriod> Weekly </sy:updatePeriod>
+        <sy:updatePeriod>fortnightly</sy:updatePeriod>
+        <sy:updatePeriod></sy:updatePeriod>
+        <sy:updateFrequency>0</sy:updateFrequency>
+        <sy:updateFrequency>-1</sy:updateFrequency>
+        <sy:updateFrequency>4294967296</sy:updateFrequency>
+        <sy:updateBase>2000-01-01T12:00+00:00</sy:updateBase>
+        <sy:updateLimit>1</sy:updateLimit>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ChannelElem::Sy(Elem::UpdatePeriod(period))) = channel_iter.next() {
+            assert_eq!(Some(Period::Weekly), period.value());
+            assert_eq!(Some(604_800), period.value().map(Period::seconds));
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::UpdatePeriod(period))) = channel_iter.next() {
+            assert_eq!(None, period.value());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::UpdatePeriod(period))) = channel_iter.next() {
+            assert_eq!(None, period.value());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::UpdateFrequency(frequency))) = channel_iter.next() {
+            assert_eq!(Ok(0), frequency.value());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::UpdateFrequency(frequency))) = channel_iter.next() {
+            assert_eq!(
+                Err(value::ErrorKind::InvalidDigit),
+                frequency.value().map_err(|e| e.kind())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::UpdateFrequency(frequency))) = channel_iter.next() {
+            assert_eq!(
+                Err(value::ErrorKind::OutOfRange),
+                frequency.value().map_err(|e| e.kind())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::UpdateBase(base))) = channel_iter.next() {
+            assert_eq!(
+                Some(946_728_000),
+                base.to_date_time().map(|d| d.unix_timestamp())
+            );
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::Sy(Elem::Unknown(unknown))) = channel_iter.next() {
+            assert_eq!("updateLimit", unknown.tag_name().local().as_str());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, channel_iter.next());
+    }
+}
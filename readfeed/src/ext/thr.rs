@@ -0,0 +1,488 @@
+//! [Atom Threading Extensions][thr] describe replies to entries.
+//!
+//! Threading elements are returned as
+//! [`atom::EntryElem::Thr`][crate::atom::EntryElem::Thr]. The `thr:count` and
+//! `thr:updated` attributes of `replies` links are available from
+//! [`atom::Link`][crate::atom::Link].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::atom::{self, Elem, EntryElem, FeedElem};
+//! use readfeed::ext::thr;
+//!
+//! let input = r#"
+//! <feed xmlns="http://www.w3.org/2005/Atom" xmlns:thr="http://purl.org/syndication/thread/1.0">
+//!     <entry>
+//!         <id>tag:example.org,2005:1,1</id>
+//!         <thr:in-reply-to ref="tag:example.org,2005:1" href="https://example.org/1"/>
+//!         <link rel="replies" href="https://example.org/1,1/replies" thr:count="10"/>
+//!     </entry>
+//! </feed>
+//! "#;
+//!
+//! let mut iter = atom::Iter::new(input);
+//!
+//! let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! assert!(matches!(entry_iter.next(), Some(EntryElem::Id(_))));
+//!
+//! if let Some(EntryElem::Thr(thr::Elem::InReplyTo(in_reply_to))) = entry_iter.next() {
+//!     assert_eq!(Some("tag:example.org,2005:1"), in_reply_to.reference().map(|v| v.as_str()));
+//! } else {
+//!     panic!();
+//! }
+//! if let Some(EntryElem::Link(link)) = entry_iter.next() {
+//!     assert_eq!(Some("replies"), link.rel().map(|v| v.as_str()));
+//!     assert_eq!(Some(10), link.thr_count());
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [thr]: https://datatracker.ietf.org/doc/html/rfc4685
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use maybe_xml::token::{
+    prop::{AttributeValue, Attributes, TagName},
+    Token,
+};
+
+#[cfg(feature = "alloc")]
+use crate::atom::{EntryElem, EntryIter};
+use crate::Tag;
+
+macro_rules! content_elem {
+    ($name:ident $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<'a> {
+            tag: Tag<'a>,
+            content: &'a str,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            #[must_use]
+            pub const fn content(&self) -> &'a str {
+                self.content
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn attributes(&self) -> Option<Attributes<'a>> {
+                self.tag.attributes()
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        content_elem!($name);
+        content_elem!($($nms),+);
+    };
+}
+
+macro_rules! impl_attr {
+    ($x:ident, $fn_name:ident, $name:literal) => {
+        impl<'a> $x<'a> {
+            #[inline]
+            #[must_use]
+            pub fn $fn_name(&self) -> Option<AttributeValue<'a>> {
+                self.tag.find_attribute($name)
+            }
+        }
+    };
+}
+
+content_elem!(Unknown);
+
+impl<'a> Unknown<'a> {
+    #[inline]
+    #[must_use]
+    pub fn tag_name(&self) -> TagName<'a> {
+        self.tag.tag_name()
+    }
+}
+
+content_elem!(InReplyTo);
+impl_attr!(InReplyTo, reference, "ref");
+impl_attr!(InReplyTo, href, "href");
+impl_attr!(InReplyTo, ty, "type");
+impl_attr!(InReplyTo, source, "source");
+
+content_elem!(Total);
+
+impl<'a> Total<'a> {
+    /// The total number of unique replies.
+    #[must_use]
+    pub fn count(&self) -> Option<u64> {
+        self.content.trim().parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Elem<'a> {
+    InReplyTo(InReplyTo<'a>),
+    Total(Total<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+impl<'a> Elem<'a> {
+    pub(crate) fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty { tag, content });
+                }
+            };
+        }
+
+        return_content!("in-reply-to", InReplyTo, Elem::InReplyTo);
+        return_content!("total", Total, Elem::Total);
+
+        Elem::Unknown(Unknown { tag, content })
+    }
+}
+
+const PREFIX: &str = "thr";
+
+#[must_use]
+pub(crate) fn is_thr(tag_name: TagName<'_>) -> bool {
+    super::has_prefix(tag_name, PREFIX)
+}
+
+/// Finds a threading attribute (e.g. `thr:count`) by its local name.
+#[must_use]
+pub(crate) fn find_attribute<'a>(
+    attributes: Option<Attributes<'a>>,
+    local_name: &str,
+) -> Option<AttributeValue<'a>> {
+    attributes?.into_iter().find_map(|attr| {
+        let (prefix, local) = attr.name().as_str().split_once(':')?;
+        (prefix.eq_ignore_ascii_case(PREFIX) && local.eq_ignore_ascii_case(local_name))
+            .then(|| attr.value())
+            .flatten()
+    })
+}
+
+/// An entry in a [`ReplyTree`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReplyNode<'a> {
+    id: Option<&'a str>,
+    entry: EntryIter<'a>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ReplyNode<'a> {
+    /// The entry's `id` content.
+    #[inline]
+    #[must_use]
+    pub const fn id(&self) -> Option<&'a str> {
+        self.id
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn entry(&self) -> &EntryIter<'a> {
+        &self.entry
+    }
+}
+
+/// Entries arranged by the entries they reply to.
+///
+/// The nodes are stored in document order and refer to each other by index,
+/// so trees of any depth can be built, compared, and dropped without
+/// recursion.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ReplyTree<'a> {
+    nodes: Vec<ReplyNode<'a>>,
+    roots: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ReplyTree<'a> {
+    /// All entries in document order.
+    #[inline]
+    #[must_use]
+    pub fn nodes(&self) -> &[ReplyNode<'a>] {
+        &self.nodes
+    }
+
+    /// The entries which do not reply to another entry in the tree.
+    pub fn roots(&self) -> impl Iterator<Item = &ReplyNode<'a>> + '_ {
+        self.roots.iter().map(|&index| &self.nodes[index])
+    }
+
+    /// The replies to the entry in document order.
+    ///
+    /// The node must be from this tree.
+    pub fn children<'t>(
+        &'t self,
+        node: &'t ReplyNode<'a>,
+    ) -> impl Iterator<Item = &'t ReplyNode<'a>> + 't {
+        node.children.iter().map(|&index| &self.nodes[index])
+    }
+
+    /// The entry which the entry replies to.
+    ///
+    /// The node must be from this tree.
+    #[must_use]
+    pub fn parent(&self, node: &ReplyNode<'a>) -> Option<&ReplyNode<'a>> {
+        node.parent.map(|index| &self.nodes[index])
+    }
+}
+
+/// Reconstructs the reply tree from a set of entries.
+///
+/// An entry is a child of the first entry referenced by one of its
+/// `thr:in-reply-to` elements which is in the set. Entries which do not reply
+/// to an entry in the set are roots in document order.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn reply_tree<'a, I>(entries: I) -> ReplyTree<'a>
+where
+    I: IntoIterator<Item = EntryIter<'a>>,
+{
+    let mut refs = Vec::new();
+    let mut nodes = entries
+        .into_iter()
+        .map(|entry| {
+            let mut id = None;
+            let mut entry_refs = Vec::new();
+            for elem in entry.clone() {
+                match elem {
+                    EntryElem::Id(value) => id = Some(value.content().trim()),
+                    EntryElem::Thr(Elem::InReplyTo(in_reply_to)) => {
+                        if let Some(reference) = in_reply_to.reference() {
+                            entry_refs.push(reference.as_str().trim());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            refs.push(entry_refs);
+            ReplyNode {
+                id,
+                entry,
+                parent: None,
+                children: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut indexes = BTreeMap::new();
+    for (index, node) in nodes.iter().enumerate() {
+        if let Some(id) = node.id {
+            indexes.entry(id).or_insert(index);
+        }
+    }
+
+    let mut replies = vec![Vec::new(); nodes.len()];
+    let mut is_root = vec![true; nodes.len()];
+    for (index, refs) in refs.iter().enumerate() {
+        if let Some(&parent) = refs
+            .iter()
+            .find_map(|r| indexes.get(r))
+            .filter(|&&parent| parent != index)
+        {
+            replies[parent].push(index);
+            is_root[index] = false;
+        }
+    }
+
+    // Entries which only reply to each other form a cycle without a root, so
+    // the first unvisited entry of a cycle becomes a root.
+    let mut visited = vec![false; nodes.len()];
+    let mut roots = Vec::new();
+    let mut stack = Vec::new();
+    let starts = (0..nodes.len())
+        .filter(|&index| is_root[index])
+        .chain(0..nodes.len());
+    for start in starts {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        roots.push(start);
+        stack.push(start);
+        while let Some(index) = stack.pop() {
+            for &child in &replies[index] {
+                if !visited[child] {
+                    visited[child] = true;
+                    nodes[index].children.push(child);
+                    nodes[child].parent = Some(index);
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    ReplyTree { nodes, roots }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::atom::{self, FeedElem};
+
+    #[test]
+    fn eval_reply_tree() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:thr="http://purl.org/syndication/thread/1.0">
+    <entry>
+        <id>urn:1</id>
+        <thr:total>2</thr:total>
+    </entry>
+    <entry>
+        <id>urn:1.1</id>
+        <thr:in-reply-to ref="urn:1"/>
+    </entry>
+    <entry>
+        <id>urn:1.1.1</id>
+        <thr:in-reply-to ref="urn:elsewhere"/>
+        <thr:in-reply-to ref="urn:1.1"/>
+    </entry>
+    <entry>
+        <id>urn:2</id>
+        <thr:in-reply-to ref="urn:elsewhere"/>
+    </entry>
+    <entry>
+        <id>urn:3</id>
+        <thr:in-reply-to ref="urn:4"/>
+    </entry>
+    <entry>
+        <id>urn:4</id>
+        <thr:in-reply-to ref="urn:3"/>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = atom::Iter::new(input);
+        let Some(atom::Elem::Feed(feed_iter)) = iter.next() else {
+            panic!();
+        };
+
+        let entries = feed_iter.filter_map(|elem| match elem {
+            FeedElem::Entry(entry) => Some(entry),
+            _ => None,
+        });
+
+        let tree = reply_tree(entries);
+        let roots = tree.roots().collect::<Vec<_>>();
+        assert_eq!(
+            vec![Some("urn:1"), Some("urn:2"), Some("urn:3")],
+            roots.iter().map(|node| node.id()).collect::<Vec<_>>()
+        );
+
+        let children = tree.children(roots[0]).collect::<Vec<_>>();
+        assert_eq!(1, children.len());
+        assert_eq!(Some("urn:1.1"), children[0].id());
+        assert_eq!(
+            Some("urn:1"),
+            tree.parent(children[0]).and_then(ReplyNode::id)
+        );
+        let grandchildren = tree.children(children[0]).collect::<Vec<_>>();
+        assert_eq!(1, grandchildren.len());
+        assert_eq!(Some("urn:1.1.1"), grandchildren[0].id());
+        assert_eq!(0, tree.children(grandchildren[0]).count());
+
+        assert_eq!(0, tree.children(roots[1]).count());
+        assert_eq!(None, tree.parent(roots[1]));
+
+        let children = tree.children(roots[2]).collect::<Vec<_>>();
+        assert_eq!(1, children.len());
+        assert_eq!(Some("urn:4"), children[0].id());
+        assert_eq!(0, tree.children(children[0]).count());
+
+        let mut entry = roots[0].entry().clone();
+        assert!(matches!(entry.next(), Some(EntryElem::Id(_))));
+        if let Some(EntryElem::Thr(Elem::Total(total))) = entry.next() {
+            assert_eq!(Some(2), total.count());
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn eval_deep_reply_tree() {
+        use alloc::{format, string::String};
+
+        let depth = 100_000;
+        let mut input = String::from(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:thr="http://purl.org/syndication/thread/1.0">"#,
+        );
+        input.push_str("<entry><id>urn:0</id></entry>");
+        for index in 1..depth {
+            input.push_str(&format!(
+                r#"<entry><id>urn:{index}</id><thr:in-reply-to ref="urn:{}"/></entry>"#,
+                index - 1
+            ));
+        }
+        input.push_str("</feed>");
+
+        let mut iter = atom::Iter::new(&input);
+        let Some(atom::Elem::Feed(feed_iter)) = iter.next() else {
+            panic!();
+        };
+        let tree = reply_tree(feed_iter.filter_map(|elem| match elem {
+            FeedElem::Entry(entry) => Some(entry),
+            _ => None,
+        }));
+
+        assert_eq!(depth, tree.nodes().len());
+        assert_eq!(1, tree.roots().count());
+        let mut node = tree.roots().next().unwrap();
+        let mut levels = 1;
+        while let Some(child) = tree.children(node).next() {
+            node = child;
+            levels += 1;
+        }
+        assert_eq!(depth, levels);
+        assert_eq!(Some("urn:99999"), node.id());
+        assert_eq!(tree.clone(), tree);
+    }
+
+    #[test]
+    fn eval_thr_link_attributes() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:THR="http://purl.org/syndication/thread/1.0">
+    <link rel="replies" href="/replies" THR:count=" 12 " THR:updated="2024-01-01T00:00:00Z"/>
+    <link rel="replies" href="/replies" thr:count="many"/>
+    <link rel="replies" href="/replies" count="3"/>
+</feed>
+        "#;
+
+        let mut iter = atom::Iter::new(input);
+        let Some(atom::Elem::Feed(feed_iter)) = iter.next() else {
+            panic!();
+        };
+        let links = feed_iter
+            .filter_map(|elem| match elem {
+                FeedElem::Link(link) => Some(link),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(Some(12), links[0].thr_count());
+        assert_eq!(
+            Some("2024-01-01T00:00:00Z"),
+            links[0].thr_updated().map(|v| v.as_str())
+        );
+        assert_eq!(None, links[1].thr_count());
+        assert_eq!(None, links[2].thr_count());
+    }
+}
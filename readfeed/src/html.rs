@@ -8,9 +8,119 @@ use maybe_xml::{
     Reader,
 };
 
+/// Media types which only identify feeds.
+const FEED_TYPES: [&str; 4] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+    "application/rdf+xml",
+];
+
+/// Media types which are used for feeds and for other documents.
+const GENERIC_FEED_TYPES: [&str; 2] = ["application/json", "text/xml"];
+
+/// How likely a `link` element refers to a feed.
+///
+/// A link has a feed relation if its `rel` attribute has the `alternate` or
+/// `feed` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+    /// The link has the `alternate` relation without a `type` or `hreflang`
+    /// attribute.
+    Low,
+    /// The link has a feed relation and a generic XML or JSON type, the `feed`
+    /// relation without a `type` attribute, or a feed type without a feed
+    /// relation.
+    Medium,
+    /// The link has a feed relation and a feed type such as
+    /// `application/atom+xml`.
+    High,
+}
+
+/// A `link` element which refers to a feed.
+///
+/// Links with the `alternate` relation and a `type` which is not a feed type
+/// (e.g. `application/pdf`) are not feed links. Neither are links with the
+/// `alternate` relation and a `hreflang` attribute but no `type`, which usually
+/// refer to translations of the page.
+///
+/// Attribute values are returned as is without decoding character references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeedLink<'a> {
+    confidence: Confidence,
+    href: &'a str,
+    rel: Option<&'a str>,
+    ty: Option<&'a str>,
+    title: Option<&'a str>,
+    hreflang: Option<&'a str>,
+    media: Option<&'a str>,
+}
+
+impl<'a> FeedLink<'a> {
+    /// How likely the link refers to a feed.
+    #[inline]
+    #[must_use]
+    pub const fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    /// The URL of the feed.
+    #[inline]
+    #[must_use]
+    pub const fn href(&self) -> &'a str {
+        self.href
+    }
+
+    /// The link relations (e.g. `alternate`).
+    #[inline]
+    #[must_use]
+    pub const fn rel(&self) -> Option<&'a str> {
+        self.rel
+    }
+
+    /// The link relation keywords of the `rel` attribute.
+    pub fn rels(&self) -> impl Iterator<Item = &'a str> {
+        self.rel.unwrap_or_default().split_ascii_whitespace()
+    }
+
+    /// If the `rel` attribute has the keyword in any ASCII case.
+    #[must_use]
+    pub fn has_rel(&self, rel: &str) -> bool {
+        self.rels().any(|value| value.eq_ignore_ascii_case(rel))
+    }
+
+    /// The media type of the feed (e.g. `application/atom+xml`).
+    #[inline]
+    #[must_use]
+    pub const fn ty(&self) -> Option<&'a str> {
+        self.ty
+    }
+
+    /// The title of the feed (e.g. `Comments`).
+    #[inline]
+    #[must_use]
+    pub const fn title(&self) -> Option<&'a str> {
+        self.title
+    }
+
+    /// The language of the feed.
+    #[inline]
+    #[must_use]
+    pub const fn hreflang(&self) -> Option<&'a str> {
+        self.hreflang
+    }
+
+    /// The media query which the feed applies to.
+    #[inline]
+    #[must_use]
+    pub const fn media(&self) -> Option<&'a str> {
+        self.media
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Elem<'a> {
-    FeedUrl(&'a str),
+    FeedLink(FeedLink<'a>),
     BaseUrl(&'a str),
 }
 
@@ -19,47 +129,71 @@ fn is_link_tag_name(name: TagName<'_>) -> bool {
     name.as_str().eq_ignore_ascii_case("link")
 }
 
-fn eval_link_tag_attributes<'a>(attributes: Attributes<'a>) -> Option<&'a str> {
-    let mut href: Option<&'a str> = None;
-    let mut rel: Option<&'a str> = None;
-    let mut ty: Option<&'a str> = None;
+fn eval_link_tag_attributes(attributes: Attributes<'_>) -> Option<FeedLink<'_>> {
+    let mut href: Option<&str> = None;
+    let mut link = FeedLink {
+        confidence: Confidence::Low,
+        href: "",
+        rel: None,
+        ty: None,
+        title: None,
+        hreflang: None,
+        media: None,
+    };
 
     for attr in attributes {
         let name = attr.name().as_str();
+        let Some(val) = attr.value().map(|val| val.as_str()) else {
+            continue;
+        };
 
         if name.eq_ignore_ascii_case("href") {
-            if let Some(val) = attr.value() {
-                href = Some(val.as_str());
-            }
+            href = Some(val);
+        } else if name.eq_ignore_ascii_case("rel") {
+            link.rel = Some(val);
+        } else if name.eq_ignore_ascii_case("type") {
+            link.ty = Some(val);
+        } else if name.eq_ignore_ascii_case("title") {
+            link.title = Some(val);
+        } else if name.eq_ignore_ascii_case("hreflang") {
+            link.hreflang = Some(val);
+        } else if name.eq_ignore_ascii_case("media") {
+            link.media = Some(val);
         }
+    }
 
-        if name.eq_ignore_ascii_case("rel") {
-            if let Some(val) = attr.value() {
-                rel = Some(val.as_str());
-            }
-        }
+    link.href = href?;
+    link.confidence = classify(&link)?;
+    Some(link)
+}
 
-        if name.eq_ignore_ascii_case("type") {
-            if let Some(val) = attr.value() {
-                ty = Some(val.as_str());
-            }
+/// Classifies a link, or returns `None` if it does not refer to a feed.
+#[must_use]
+fn classify(link: &FeedLink<'_>) -> Option<Confidence> {
+    let has_feed_rel = link.has_rel("alternate") || link.has_rel("feed");
+    let essence = link
+        .ty
+        .map(|ty| ty.split(';').next().unwrap_or_default().trim())
+        .filter(|essence| !essence.is_empty());
+    let Some(essence) = essence else {
+        if link.has_rel("feed") {
+            return Some(Confidence::Medium);
         }
-    }
+        return (has_feed_rel && link.hreflang.is_none()).then_some(Confidence::Low);
+    };
 
-    let href = href?;
-
-    let is_feed_link = rel
-        .map(str::trim)
-        .map(|rel| rel.eq_ignore_ascii_case("alternate") || rel.eq_ignore_ascii_case("feed"))
-        .unwrap_or_default()
-        || ty
-            .map(str::trim)
-            .map(|ty| {
-                ty.eq_ignore_ascii_case("application/rss+xml")
-                    || ty.eq_ignore_ascii_case("application/atom+xml")
-            })
-            .unwrap_or_default();
-    is_feed_link.then_some(href)
+    let is_ty = |ty: &&str| ty.eq_ignore_ascii_case(essence);
+    if FEED_TYPES.iter().any(is_ty) {
+        Some(if has_feed_rel {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        })
+    } else if has_feed_rel && GENERIC_FEED_TYPES.iter().any(is_ty) {
+        Some(Confidence::Medium)
+    } else {
+        None
+    }
 }
 
 #[must_use]
@@ -108,8 +242,8 @@ fn eval<'a>(iter: &mut maybe_xml::IntoIter<'a>) -> Option<Elem<'a>> {
             token::Ty::StartTag(tag) => {
                 let tag_name = tag.name();
                 if is_link_tag_name(tag_name) {
-                    if let Some(feed_url) = tag.attributes().and_then(eval_link_tag_attributes) {
-                        return Some(Elem::FeedUrl(feed_url));
+                    if let Some(link) = tag.attributes().and_then(eval_link_tag_attributes) {
+                        return Some(Elem::FeedLink(link));
                     }
                 }
 
@@ -122,8 +256,8 @@ fn eval<'a>(iter: &mut maybe_xml::IntoIter<'a>) -> Option<Elem<'a>> {
             token::Ty::EmptyElementTag(tag) => {
                 let tag_name = tag.name();
                 if is_link_tag_name(tag_name) {
-                    if let Some(feed_url) = tag.attributes().and_then(eval_link_tag_attributes) {
-                        return Some(Elem::FeedUrl(feed_url));
+                    if let Some(link) = tag.attributes().and_then(eval_link_tag_attributes) {
+                        return Some(Elem::FeedLink(link));
                     }
                 }
 
@@ -154,9 +288,65 @@ mod tests {
         let input: &str = include_str!("../tests/resources/html-1.html");
         let mut iter = Iter::new(input);
 
-        assert_eq!(Some(Elem::FeedUrl("/feed.xml")), iter.next());
+        assert_eq!(
+            Some(Elem::FeedLink(FeedLink {
+                confidence: Confidence::High,
+                href: "/feed.xml",
+                rel: Some("alternate"),
+                ty: Some("application/rss+xml"),
+                title: Some("Example RSS"),
+                hreflang: None,
+                media: None,
+            })),
+            iter.next()
+        );
         // There are 2 "/feed.xml" links
-        assert_eq!(Some(Elem::FeedUrl("/feed.xml")), iter.next());
+        let Some(Elem::FeedLink(link)) = iter.next() else {
+            panic!("expected a feed link");
+        };
+        assert_eq!("/feed.xml", link.href());
+        assert_eq!(Some("feed"), link.rel());
+        assert_eq!(Some("Example"), link.title());
+        assert_eq!(Confidence::High, link.confidence());
         assert_eq!(None, iter.next());
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn classify_feed_links() {
+        use alloc::{vec, vec::Vec};
+
+        let input = r#"
+<link rel="alternate home" type="application/atom+xml" href="/atom.xml">
+<link rel="Alternate" type="application/feed+json; charset=utf-8" href="/feed.json">
+<link rel="alternate" type="application/json" href="/api.json">
+<link rel="feed" href="/posts">
+<link rel="alternate" href="/print">
+<link rel="search" type="application/rss+xml" href="/search.xml">
+<link rel="alternate" type="application/pdf" href="/page.pdf">
+<link rel="alternate" hreflang="de" href="/de/">
+<link rel="manifest" type="application/json" href="/manifest.json">
+<link rel="alternate" type="text/html" href="/amp">
+<link rel="alternate" hreflang="fr" type="application/rss+xml" href="/fr/feed.xml">
+        "#;
+
+        let links = Iter::new(input)
+            .filter_map(|elem| match elem {
+                Elem::FeedLink(link) => Some((link.href(), link.confidence())),
+                Elem::BaseUrl(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("/atom.xml", Confidence::High),
+                ("/feed.json", Confidence::High),
+                ("/api.json", Confidence::Medium),
+                ("/posts", Confidence::Medium),
+                ("/print", Confidence::Low),
+                ("/search.xml", Confidence::Medium),
+                ("/fr/feed.xml", Confidence::High),
+            ],
+            links
+        );
+    }
 }
@@ -0,0 +1,472 @@
+//! Computes stable identities of RSS items and Atom entries.
+//!
+//! The identity is used to recognize an item or entry across fetches of a
+//! feed. In order of preference, the identity is:
+//!
+//! 1. the `guid` of an RSS item or the `id` of an Atom entry
+//! 2. the `link` of an RSS item or the alternate `link` of an Atom entry
+//! 3. a hash of the title, date, and enclosure URL
+//!
+//! The [`Strategy`] reports which one was used. Identities are only equal if
+//! both the strategy and the key are equal.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::identity::{self, Strategy};
+//!
+//! let input = "
+//! <rss>
+//!     <channel>
+//!         <item>
+//!             <guid isPermaLink=\"false\">1</guid>
+//!             <link>https://example.com/1</link>
+//!         </item>
+//!         <item>
+//!             <link>https://example.com/2</link>
+//!         </item>
+//!         <item>
+//!             <title>Lorem ipsum</title>
+//!             <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+//!         </item>
+//!         <item>
+//!             <guid isPermaLink=\"false\">1</guid>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! ";
+//!
+//! let identities = identity::identities(input);
+//! assert_eq!(
+//!     vec![Strategy::Id, Strategy::Link, Strategy::Hash, Strategy::Id],
+//!     identities.iter().map(|id| id.strategy()).collect::<Vec<_>>()
+//! );
+//! assert_eq!("https://example.com/2", identities[1].key());
+//!
+//! let duplicates = identity::find_duplicates(identities);
+//! assert_eq!(vec!["1"], duplicates.iter().map(|id| id.key()).collect::<Vec<_>>());
+//! ```
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{atom, date::DateTime, ext::dc, rss, xml, Ty};
+
+/// How an identity was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strategy {
+    /// The `guid` of an RSS item or the `id` of an Atom entry.
+    Id,
+    /// The `link` of an RSS item or the alternate `link` of an Atom entry.
+    Link,
+    /// A hash of the title, date, and enclosure URL.
+    Hash,
+}
+
+/// The stable identity of an RSS item or Atom entry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Identity<'a> {
+    strategy: Strategy,
+    key: Cow<'a, str>,
+}
+
+impl<'a> Identity<'a> {
+    /// The strategy used to compute the identity.
+    #[inline]
+    #[must_use]
+    pub const fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// The id or link with character references decoded, or the hash as 16
+    /// hexadecimal digits.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Converts the identity into an owned value.
+    #[must_use]
+    pub fn into_owned(self) -> Identity<'static> {
+        Identity {
+            strategy: self.strategy,
+            key: Cow::Owned(self.key.into_owned()),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Identity<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let strategy = match self.strategy {
+            Strategy::Id => "id",
+            Strategy::Link => "link",
+            Strategy::Hash => "hash",
+        };
+        write!(f, "{strategy}:{}", self.key)
+    }
+}
+
+/// A 64-bit FNV-1a hash.
+#[derive(Debug, Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    /// Hashes the field followed by a separator which cannot occur in UTF-8.
+    #[must_use]
+    fn field(self, value: &str) -> Self {
+        let hash = value
+            .bytes()
+            .chain(core::iter::once(0xff))
+            .fold(self.0, |hash, b| {
+                (hash ^ u64::from(b)).wrapping_mul(Self::PRIME)
+            });
+        Self(hash)
+    }
+}
+
+/// A date of an item or entry as written in the document and as parsed.
+#[derive(Debug, Clone)]
+pub(crate) struct FieldDate<'a> {
+    pub(crate) value: Cow<'a, str>,
+    pub(crate) date_time: Option<DateTime>,
+}
+
+impl<'a> FieldDate<'a> {
+    #[must_use]
+    fn new(value: &'a str, date_time: Option<DateTime>) -> Option<Self> {
+        decode_non_empty(value).map(|value| Self { value, date_time })
+    }
+}
+
+/// The fields of an item or entry which are shared by identities, diffs, and
+/// planets.
+///
+/// Text values have character references decoded and are trimmed. Empty values
+/// are ignored.
+#[derive(Debug, Default)]
+pub(crate) struct Fields<'a> {
+    pub(crate) id: Option<Cow<'a, str>>,
+    pub(crate) link: Option<Cow<'a, str>>,
+    pub(crate) title: Option<Cow<'a, str>>,
+    pub(crate) published: Option<FieldDate<'a>>,
+    pub(crate) updated: Option<FieldDate<'a>>,
+    pub(crate) enclosures: Vec<Cow<'a, str>>,
+}
+
+/// Decodes the value and returns it if it is not empty.
+#[must_use]
+pub(crate) fn decode_non_empty(value: &str) -> Option<Cow<'_, str>> {
+    match xml::decode(value) {
+        Cow::Borrowed(value) => Some(Cow::Borrowed(value.trim())),
+        Cow::Owned(value) => Some(Cow::Owned(String::from(value.trim()))),
+    }
+    .filter(|value| !value.is_empty())
+}
+
+impl<'a> Fields<'a> {
+    /// Extracts the fields of an RSS item.
+    ///
+    /// The published date is the `pubDate`, or else the `dc:date`. RSS items
+    /// have no updated date.
+    #[must_use]
+    pub(crate) fn from_item(item: rss::ChannelItemIter<'a>) -> Self {
+        let mut fields = Fields::default();
+        let mut dc_date = None;
+        for elem in item {
+            match elem {
+                rss::ItemElem::Guid(guid) if fields.id.is_none() => {
+                    fields.id = decode_non_empty(guid.content());
+                }
+                rss::ItemElem::Link(link) if fields.link.is_none() => {
+                    fields.link = decode_non_empty(link.content());
+                }
+                rss::ItemElem::Title(title) if fields.title.is_none() => {
+                    fields.title = decode_non_empty(title.content());
+                }
+                rss::ItemElem::PubDate(date) if fields.published.is_none() => {
+                    fields.published = FieldDate::new(date.content(), date.to_date_time());
+                }
+                rss::ItemElem::Dc(dc::Elem::Date(date)) if dc_date.is_none() => {
+                    dc_date = FieldDate::new(date.content(), date.to_date_time());
+                }
+                rss::ItemElem::Enclosure(enclosure) => {
+                    fields.enclosures.extend(
+                        enclosure
+                            .url()
+                            .and_then(|url| decode_non_empty(url.as_str())),
+                    );
+                }
+                _ => {}
+            }
+        }
+        if fields.published.is_none() {
+            fields.published = dc_date;
+        }
+        fields
+    }
+
+    /// Extracts the fields of an Atom entry.
+    ///
+    /// The link is the first `link` element with the `alternate` relation or
+    /// without a `rel` attribute. The enclosures are the `link` elements with
+    /// the `enclosure` relation.
+    #[must_use]
+    pub(crate) fn from_entry(entry: atom::EntryIter<'a>) -> Self {
+        let mut fields = Fields::default();
+        for elem in entry {
+            match elem {
+                atom::EntryElem::Id(id) if fields.id.is_none() => {
+                    fields.id = decode_non_empty(id.content());
+                }
+                atom::EntryElem::Link(link) => {
+                    let rel = link.rel().map_or("alternate", |rel| rel.as_str().trim());
+                    let href = link.href().and_then(|href| decode_non_empty(href.as_str()));
+                    if rel.eq_ignore_ascii_case("alternate") && fields.link.is_none() {
+                        fields.link = href;
+                    } else if rel.eq_ignore_ascii_case("enclosure") {
+                        fields.enclosures.extend(href);
+                    }
+                }
+                atom::EntryElem::Title(title) if fields.title.is_none() => {
+                    fields.title = decode_non_empty(title.content());
+                }
+                atom::EntryElem::Updated(date) if fields.updated.is_none() => {
+                    fields.updated = FieldDate::new(date.content(), date.to_date_time());
+                }
+                atom::EntryElem::Published(date) if fields.published.is_none() => {
+                    fields.published = FieldDate::new(date.content(), date.to_date_time());
+                }
+                _ => {}
+            }
+        }
+        fields
+    }
+
+    /// The updated date, or else the published date.
+    #[must_use]
+    pub(crate) fn date(&self) -> Option<&FieldDate<'a>> {
+        self.updated.as_ref().or(self.published.as_ref())
+    }
+
+    /// The identity of the item or entry.
+    #[must_use]
+    pub(crate) fn identity(&self) -> Identity<'a> {
+        if let Some(id) = &self.id {
+            return Identity {
+                strategy: Strategy::Id,
+                key: id.clone(),
+            };
+        }
+        if let Some(link) = &self.link {
+            return Identity {
+                strategy: Strategy::Link,
+                key: link.clone(),
+            };
+        }
+        let hash = Fnv1a(Fnv1a::OFFSET_BASIS)
+            .field(self.title.as_deref().unwrap_or_default())
+            .field(self.date().map(|date| &*date.value).unwrap_or_default())
+            .field(
+                self.enclosures
+                    .first()
+                    .map(|url| &**url)
+                    .unwrap_or_default(),
+            );
+        Identity {
+            strategy: Strategy::Hash,
+            key: Cow::Owned(alloc::format!("{:016x}", hash.0)),
+        }
+    }
+}
+
+/// Computes the identity of an RSS item.
+///
+/// The hash uses the `title`, the `pubDate` (or `dc:date` if there is no
+/// `pubDate` element), and the `url` of the first `enclosure`.
+#[must_use]
+pub fn item_identity(item: rss::ChannelItemIter<'_>) -> Identity<'_> {
+    Fields::from_item(item).identity()
+}
+
+/// Computes the identity of an Atom entry.
+///
+/// The link is the first `link` element with the `alternate` relation or
+/// without a `rel` attribute. The hash uses the `title`, the `updated` date
+/// (or `published` date if there is no `updated` element), and the `href` of
+/// the first `link` with the `enclosure` relation.
+#[must_use]
+pub fn entry_identity(entry: atom::EntryIter<'_>) -> Identity<'_> {
+    Fields::from_entry(entry).identity()
+}
+
+/// Computes the identities of the items or entries of a feed document in
+/// document order.
+///
+/// The type of document is detected with [`detect_type()`][crate::detect_type].
+/// An empty list is returned for documents which are not RSS or Atom feeds.
+#[must_use]
+pub fn identities(input: &str) -> Vec<Identity<'_>> {
+    match crate::detect_type(input) {
+        Ty::Atom => atom::Iter::new(input)
+            .filter_map(|elem| match elem {
+                atom::Elem::Feed(feed) => Some(feed),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|elem| match elem {
+                atom::FeedElem::Entry(entry) => Some(entry_identity(entry)),
+                _ => None,
+            })
+            .collect(),
+        Ty::Rss => rss::Iter::new(input)
+            .filter_map(|elem| match elem {
+                rss::Elem::Rss(rss) => Some(rss),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|elem| match elem {
+                rss::RssElem::Channel(channel) => Some(channel),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|elem| match elem {
+                rss::ChannelElem::Item(item) => Some(item_identity(item)),
+                _ => None,
+            })
+            .collect(),
+        Ty::Json | Ty::Unknown | Ty::XmlOrHtml => Vec::new(),
+    }
+}
+
+/// Finds the identities which occur more than once.
+///
+/// Each duplicated identity is returned once in the order of its second
+/// occurrence.
+#[must_use]
+pub fn find_duplicates<'a, I>(identities: I) -> Vec<Identity<'a>>
+where
+    I: IntoIterator<Item = Identity<'a>>,
+{
+    let mut seen = BTreeSet::new();
+    let mut duplicates = BTreeSet::new();
+    let mut output = Vec::new();
+    for identity in identities {
+        if seen.contains(&identity) {
+            if duplicates.insert(identity.to_string()) {
+                output.push(identity);
+            }
+        } else {
+            seen.insert(identity);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn entry_identities() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <entry>
+        <id>urn:uuid:1</id>
+        <link href="https://example.com/1"/>
+    </entry>
+    <entry>
+        <link rel="enclosure" href="https://example.com/2.mp3"/>
+        <link rel="alternate" href="https://example.com/2?a=1&amp;b=2"/>
+    </entry>
+    <entry>
+        <title>Lorem</title>
+        <published>2024-01-01T00:00:00Z</published>
+        <link rel="enclosure" href="https://example.com/3.mp3"/>
+    </entry>
+    <entry>
+        <title>Lorem</title>
+        <published>2024-01-01T00:00:00Z</published>
+        <link rel="enclosure" href="https://example.com/3.mp3"/>
+    </entry>
+    <entry>
+        <title>Lorem</title>
+        <published>2024-01-02T00:00:00Z</published>
+        <link rel="enclosure" href="https://example.com/3.mp3"/>
+    </entry>
+    <entry>
+        <id> urn:uuid:1 </id>
+    </entry>
+</feed>
+        "#;
+
+        let identities = identities(input);
+        assert_eq!(
+            vec![
+                "id:urn:uuid:1",
+                "link:https://example.com/2?a=1&b=2",
+                "hash:7d5b7b7fce8c2fdb",
+                "hash:7d5b7b7fce8c2fdb",
+                "hash:4041644fe183eb44",
+                "id:urn:uuid:1",
+            ],
+            identities
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+
+        let duplicates = find_duplicates(identities);
+        assert_eq!(
+            vec!["hash:7d5b7b7fce8c2fdb", "id:urn:uuid:1"],
+            duplicates
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn item_identities() {
+        let input = r#"
+<rss xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <channel>
+        <item>
+            <title>Lorem</title>
+            <dc:date>2024-01-01T00:00:00Z</dc:date>
+        </item>
+        <item>
+            <title>Lorem</title>
+            <dc:date>2024-01-02T00:00:00Z</dc:date>
+        </item>
+        <item>
+            <title>Lorem</title>
+            <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+            <dc:date>2024-01-02T00:00:00Z</dc:date>
+        </item>
+        <item>
+            <title>Lorem</title>
+            <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let identities = identities(input);
+        assert!(identities
+            .iter()
+            .all(|identity| identity.strategy() == Strategy::Hash));
+        assert_ne!(identities[0], identities[1]);
+        assert_ne!(identities[1], identities[2]);
+        assert_eq!(identities[2], identities[3]);
+    }
+}
@@ -0,0 +1,644 @@
+//! Picks the best image to represent an RSS item or Atom entry (e.g. as the
+//! thumbnail of a card).
+//!
+//! The candidates are, in order of preference:
+//!
+//! 1. `media:thumbnail` elements
+//! 2. `media:content` elements with an image type
+//! 3. `itunes:image` elements
+//! 4. `enclosure` elements with an image type
+//! 5. `link` elements with the `enclosure` relation and an image type
+//! 6. the first `img` element in the HTML content
+//!
+//! When there are several candidates of the same kind, the largest stated size
+//! is preferred. Otherwise, the first candidate is used.
+//!
+//! If an item or entry has no image, the image of the channel
+//! ([`channel_image()`]) or feed ([`feed_image()`]) can be used instead.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::image::{self, Source};
+//! use readfeed::rss::{self, ChannelElem, Elem, RssElem};
+//!
+//! let input = r#"
+//! <rss xmlns:media="http://search.yahoo.com/mrss/">
+//!     <channel>
+//!         <image><url>https://example.com/logo.png</url></image>
+//!         <item>
+//!             <description>&lt;img src="/a.jpg"&gt;</description>
+//!             <media:thumbnail url="https://example.com/a-thumb.jpg" width="320" height="180"/>
+//!         </item>
+//!         <item>
+//!             <description>No images</description>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! let channel_image = image::channel_image(channel_iter.clone());
+//! let images = channel_iter
+//!     .filter_map(|elem| match elem {
+//!         ChannelElem::Item(item) => image::item_image(item).or_else(|| channel_image.clone()),
+//!         _ => None,
+//!     })
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!("https://example.com/a-thumb.jpg", images[0].url());
+//! assert_eq!(Source::MediaThumbnail, images[0].source());
+//! assert_eq!((Some(320), Some(180)), (images[0].width(), images[0].height()));
+//!
+//! assert_eq!("https://example.com/logo.png", images[1].url());
+//! assert_eq!(Source::ChannelImage, images[1].source());
+//! ```
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use maybe_xml::token::{self, prop::AttributeValue};
+
+use crate::{
+    atom,
+    ext::{content, itunes, media},
+    rss,
+    text::{self, HtmlToken},
+    uri::Base,
+    value, xml,
+};
+
+/// Where an image was found.
+///
+/// The variants are ordered from the most preferred to the least preferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Source {
+    /// A `media:thumbnail` element.
+    MediaThumbnail,
+    /// A `media:content` element with an image type.
+    MediaContent,
+    /// An `itunes:image` element.
+    ItunesImage,
+    /// An RSS `enclosure` element with an image type.
+    Enclosure,
+    /// An Atom `link` element with the `enclosure` relation and an image type.
+    EnclosureLink,
+    /// The first `img` element in the HTML content.
+    Content,
+    /// The `url` of an RSS channel's `image` element or a channel
+    /// `itunes:image` element.
+    ChannelImage,
+    /// An Atom feed's `logo` element.
+    Logo,
+    /// An Atom feed's `icon` element.
+    Icon,
+}
+
+/// An image and its size if stated by the feed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image<'a> {
+    url: Cow<'a, str>,
+    width: Option<u32>,
+    height: Option<u32>,
+    source: Source,
+    base: Base<'a>,
+}
+
+impl<'a> Image<'a> {
+    #[must_use]
+    fn new(url: Cow<'a, str>, source: Source, base: Base<'a>) -> Option<Self> {
+        (!url.trim().is_empty()).then_some(Self {
+            url,
+            width: None,
+            height: None,
+            source,
+            base,
+        })
+    }
+
+    #[must_use]
+    fn with_size(mut self, width: Option<u32>, height: Option<u32>) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// The URL as written in the document with character references decoded.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        self.url.trim()
+    }
+
+    /// The width in pixels.
+    #[inline]
+    #[must_use]
+    pub const fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    /// The height in pixels.
+    #[inline]
+    #[must_use]
+    pub const fn height(&self) -> Option<u32> {
+        self.height
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn source(&self) -> Source {
+        self.source
+    }
+
+    /// The `xml:base` values which apply to the URL.
+    #[inline]
+    #[must_use]
+    pub const fn base(&self) -> Base<'a> {
+        self.base
+    }
+
+    /// Resolves the URL against the base URI and the document URL.
+    #[must_use]
+    pub fn resolve(&self, document_url: &str) -> String {
+        self.base.resolve(document_url, self.url())
+    }
+
+    #[must_use]
+    fn area(&self) -> u64 {
+        u64::from(self.width.unwrap_or_default()) * u64::from(self.height.unwrap_or_default())
+    }
+}
+
+#[must_use]
+fn decode_attr(value: AttributeValue<'_>) -> Cow<'_, str> {
+    xml::decode(value.as_str())
+}
+
+#[must_use]
+fn parse_size(value: Option<AttributeValue<'_>>) -> Option<u32> {
+    value.and_then(|value| value::parse_u32(value.as_str()).ok())
+}
+
+#[must_use]
+fn is_image_type(ty: Option<AttributeValue<'_>>) -> bool {
+    ty.and_then(|ty| ty.as_str().trim().get(..6))
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("image/"))
+}
+
+/// The elements nested in a Media RSS `group` or `content` element.
+#[derive(Debug)]
+enum MediaChildren<'a> {
+    Group(media::GroupIter<'a>),
+    Content(media::ContentIter<'a>),
+}
+
+impl<'a> Iterator for MediaChildren<'a> {
+    type Item = media::Elem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            MediaChildren::Group(iter) => iter.next(),
+            MediaChildren::Content(iter) => iter.next(),
+        }
+    }
+}
+
+/// The best image found so far.
+#[derive(Debug, Default)]
+struct Best<'a> {
+    image: Option<Image<'a>>,
+}
+
+impl<'a> Best<'a> {
+    fn offer(&mut self, image: Option<Image<'a>>) {
+        let Some(image) = image else {
+            return;
+        };
+        let is_better = self.image.as_ref().map_or(true, |best| {
+            image.source < best.source
+                || (image.source == best.source && image.area() > best.area())
+        });
+        if is_better {
+            self.image = Some(image);
+        }
+    }
+
+    /// Offers the thumbnails and images in a Media RSS element and the
+    /// elements nested in it.
+    ///
+    /// Nested `group` and `content` elements are visited with a stack instead
+    /// of recursion so deeply nested documents cannot overflow the call stack.
+    fn offer_media(&mut self, elem: media::Elem<'a>, base: Base<'a>) {
+        let mut stack: Vec<MediaChildren<'a>> = Vec::new();
+        let mut next = Some(elem);
+        loop {
+            let elem = match next.take() {
+                Some(elem) => elem,
+                None => {
+                    let Some(children) = stack.last_mut() else {
+                        break;
+                    };
+                    match children.next() {
+                        Some(elem) => elem,
+                        None => {
+                            stack.pop();
+                            continue;
+                        }
+                    }
+                }
+            };
+            match elem {
+                media::Elem::Thumbnail(thumbnail) => {
+                    self.offer(thumbnail.url().and_then(|url| {
+                        Image::new(decode_attr(url), Source::MediaThumbnail, base).map(|image| {
+                            image.with_size(
+                                parse_size(thumbnail.width()),
+                                parse_size(thumbnail.height()),
+                            )
+                        })
+                    }));
+                }
+                media::Elem::Content(content) => {
+                    let is_image = content
+                        .medium()
+                        .is_some_and(|medium| medium.as_str().trim().eq_ignore_ascii_case("image"))
+                        || is_image_type(content.ty());
+                    if is_image {
+                        self.offer(content.url().and_then(|url| {
+                            Image::new(decode_attr(url), Source::MediaContent, base).map(|image| {
+                                image.with_size(
+                                    parse_size(content.width()),
+                                    parse_size(content.height()),
+                                )
+                            })
+                        }));
+                    }
+                    stack.push(MediaChildren::Content(content));
+                }
+                media::Elem::Group(group) => stack.push(MediaChildren::Group(group)),
+                _ => {}
+            }
+        }
+    }
+
+    fn offer_enclosure_link(&mut self, link: &atom::Link<'a>) {
+        let is_enclosure = link.rel().is_some_and(|rel| {
+            rel.as_str()
+                .split_ascii_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("enclosure"))
+        });
+        if is_enclosure && is_image_type(link.ty()) {
+            self.offer(link.href().and_then(|href| {
+                Image::new(decode_attr(href), Source::EnclosureLink, link.base())
+            }));
+        }
+    }
+
+    /// Offers the first `img` element in the HTML content if no other image
+    /// was found.
+    fn offer_content(&mut self, html: Option<(Cow<'_, str>, Base<'a>)>) {
+        if self.image.is_some() {
+            return;
+        }
+        if let Some((html, base)) = html {
+            self.offer(first_img(&html, base));
+        }
+    }
+}
+
+/// Finds the first `img` element which is not a tracking pixel.
+#[must_use]
+fn first_img<'a>(html: &str, base: Base<'a>) -> Option<Image<'a>> {
+    let mut found = None;
+    text::tokenize_html(html, |token| {
+        let tag_attributes = match token {
+            HtmlToken::Markup(token::Ty::StartTag(tag)) => Some((tag.name(), tag.attributes())),
+            HtmlToken::Markup(token::Ty::EmptyElementTag(tag)) => {
+                Some((tag.name(), tag.attributes()))
+            }
+            _ => None,
+        };
+        let Some((name, attributes)) = tag_attributes else {
+            return;
+        };
+        if found.is_some() || !name.local().as_str().eq_ignore_ascii_case("img") {
+            return;
+        }
+
+        let mut url = String::new();
+        if let Some(src) = crate::find_attribute(attributes, "src") {
+            text::push_decoded(&mut url, src.as_str());
+        }
+        let width = parse_size(crate::find_attribute(attributes, "width"));
+        let height = parse_size(crate::find_attribute(attributes, "height"));
+        if width.is_some_and(|w| w <= 1) || height.is_some_and(|h| h <= 1) {
+            return;
+        }
+        found = Image::new(Cow::Owned(url), Source::Content, base)
+            .map(|image| image.with_size(width, height));
+    });
+    found
+}
+
+/// Picks the best image for an RSS item.
+///
+/// The first `img` element in the `content:encoded` element, or in the
+/// `description` if there is no `content:encoded` element, is used if there
+/// are no other candidates.
+#[must_use]
+pub fn item_image<'a>(item: rss::ChannelItemIter<'a>) -> Option<Image<'a>> {
+    let base = item.base();
+    let mut best = Best::default();
+    let mut encoded = None;
+    let mut description = None;
+
+    for elem in item {
+        match elem {
+            rss::ItemElem::Media(elem) => best.offer_media(elem, base),
+            rss::ItemElem::Itunes(itunes::Elem::Image(image)) => {
+                best.offer(
+                    image
+                        .href()
+                        .and_then(|href| Image::new(decode_attr(href), Source::ItunesImage, base)),
+                );
+            }
+            rss::ItemElem::Enclosure(enclosure) if is_image_type(enclosure.ty()) => {
+                best.offer(enclosure.url().and_then(|url| {
+                    Image::new(decode_attr(url), Source::Enclosure, enclosure.base())
+                }));
+            }
+            rss::ItemElem::AtomLink(link) => best.offer_enclosure_link(&link),
+            rss::ItemElem::Content(content::Elem::Encoded(elem)) => {
+                encoded.get_or_insert((elem.html(), base));
+            }
+            rss::ItemElem::Description(elem) => {
+                description.get_or_insert((xml::decode(elem.content()), elem.base()));
+            }
+            _ => {}
+        }
+    }
+
+    best.offer_content(encoded.or(description));
+    best.image
+}
+
+/// Picks the best image for an Atom entry.
+///
+/// The first `img` element in the HTML or XHTML `content` element, or in the
+/// `summary` if there is no such `content` element, is used if there are no
+/// other candidates.
+#[must_use]
+pub fn entry_image<'a>(entry: atom::EntryIter<'a>) -> Option<Image<'a>> {
+    let base = entry.base();
+    let mut best = Best::default();
+    let mut content = None;
+    let mut summary = None;
+
+    let markup = |value: atom::TextConstruct| match value {
+        atom::TextConstruct::Html(html) | atom::TextConstruct::Xhtml(html) => Some(html),
+        atom::TextConstruct::Text(_) => None,
+    };
+
+    for elem in entry {
+        match elem {
+            atom::EntryElem::Media(elem) => best.offer_media(elem, base),
+            atom::EntryElem::Link(link) => best.offer_enclosure_link(&link),
+            atom::EntryElem::Content(elem) if content.is_none() => {
+                if let Ok(atom::ContentValue::Text(value)) = elem.to_content_value() {
+                    content = markup(value).map(|html| (Cow::Owned(html), elem.base()));
+                }
+            }
+            atom::EntryElem::Summary(elem) if summary.is_none() => {
+                summary =
+                    markup(elem.to_text_construct()).map(|html| (Cow::Owned(html), elem.base()));
+            }
+            _ => {}
+        }
+    }
+
+    best.offer_content(content.or(summary));
+    best.image
+}
+
+/// Finds the image of an RSS channel.
+///
+/// The `url` of the `image` element is preferred over an `itunes:image`
+/// element.
+#[must_use]
+pub fn channel_image<'a>(channel: rss::ChannelIter<'a>) -> Option<Image<'a>> {
+    let base = channel.base();
+    let mut image_url = None;
+    let mut itunes_image = None;
+
+    for elem in channel {
+        match elem {
+            rss::ChannelElem::Image(image) => {
+                let (mut url, mut width, mut height) = (None, None, None);
+                for elem in image {
+                    match elem {
+                        rss::ImageElem::Url(elem) => {
+                            url = Image::new(
+                                xml::decode(elem.content()),
+                                Source::ChannelImage,
+                                elem.base(),
+                            );
+                        }
+                        rss::ImageElem::Width(elem) => width = elem.value().ok(),
+                        rss::ImageElem::Height(elem) => height = elem.value().ok(),
+                        _ => {}
+                    }
+                }
+                if image_url.is_none() {
+                    image_url = url.map(|image| image.with_size(width, height));
+                }
+            }
+            rss::ChannelElem::Itunes(itunes::Elem::Image(image)) if itunes_image.is_none() => {
+                itunes_image = image
+                    .href()
+                    .and_then(|href| Image::new(decode_attr(href), Source::ChannelImage, base));
+            }
+            _ => {}
+        }
+    }
+
+    image_url.or(itunes_image)
+}
+
+/// Finds the image of an Atom feed.
+///
+/// The `logo` element is preferred over the `icon` element.
+#[must_use]
+pub fn feed_image<'a>(feed: atom::FeedIter<'a>) -> Option<Image<'a>> {
+    let mut best = Best::default();
+    for elem in feed {
+        match elem {
+            atom::FeedElem::Logo(logo) => {
+                best.offer(Image::new(
+                    xml::decode(logo.content()),
+                    Source::Logo,
+                    logo.base(),
+                ));
+            }
+            atom::FeedElem::Icon(icon) => {
+                best.offer(Image::new(
+                    xml::decode(icon.content()),
+                    Source::Icon,
+                    icon.base(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    best.image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn item_images() {
+        let input = r#"
+<rss xmlns:media="http://search.yahoo.com/mrss/" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+    <channel>
+        <item>
+            <description>&lt;img src="/pixel.gif" width="1"&gt;&lt;img src="/a.jpg?x=1&amp;amp;y=2" width="640"&gt;</description>
+        </item>
+        <item>
+            <enclosure url="https://example.com/a.mp3" type="audio/mpeg"/>
+            <enclosure url="https://example.com/a.png" type="image/png"/>
+            <itunes:image href="https://example.com/episode.jpg"/>
+        </item>
+        <item>
+            <media:group>
+                <media:content url="https://example.com/small.jpg" medium="image" width="100" height="100"/>
+                <media:content url="https://example.com/large.jpg" type="image/jpeg" width="800" height="600"/>
+                <media:content url="https://example.com/video.mp4" medium="video"/>
+            </media:group>
+        </item>
+        <item>
+            <description>No image</description>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = rss::Iter::new(input);
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(rss::RssElem::Channel(channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        let mut images = channel_iter.filter_map(|elem| match elem {
+            rss::ChannelElem::Item(item) => Some(item_image(item)),
+            _ => None,
+        });
+
+        let image = images.next().flatten().unwrap();
+        assert_eq!("/a.jpg?x=1&y=2", image.url());
+        assert_eq!(Source::Content, image.source());
+        assert_eq!((Some(640), None), (image.width(), image.height()));
+        assert_eq!(
+            "https://example.com/a.jpg?x=1&y=2",
+            image.resolve("https://example.com/feed.xml")
+        );
+
+        let image = images.next().flatten().unwrap();
+        assert_eq!("https://example.com/episode.jpg", image.url());
+        assert_eq!(Source::ItunesImage, image.source());
+
+        let image = images.next().flatten().unwrap();
+        assert_eq!("https://example.com/large.jpg", image.url());
+        assert_eq!(Source::MediaContent, image.source());
+        assert_eq!((Some(800), Some(600)), (image.width(), image.height()));
+
+        assert_eq!(Some(None), images.next());
+        assert_eq!(None, images.next());
+    }
+
+    #[test]
+    fn deeply_nested_media_groups() {
+        const DEPTH: usize = 2_000;
+
+        let mut input =
+            String::from(r#"<rss xmlns:media="http://search.yahoo.com/mrss/"><channel><item>"#);
+        for _ in 0..DEPTH {
+            input.push_str("<media:group>");
+        }
+        input.push_str(r#"<media:thumbnail url="https://example.com/deep.jpg"/>"#);
+        for _ in 0..DEPTH {
+            input.push_str("</media:group>");
+        }
+        input.push_str("</item></channel></rss>");
+
+        let mut iter = rss::Iter::new(&input);
+        let Some(rss::Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(rss::RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        let Some(rss::ChannelElem::Item(item)) = channel_iter.next() else {
+            panic!();
+        };
+
+        let image = item_image(item).unwrap();
+        assert_eq!("https://example.com/deep.jpg", image.url());
+        assert_eq!(Source::MediaThumbnail, image.source());
+    }
+
+    #[test]
+    fn entry_images() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom" xml:base="https://example.com/blog/">
+    <logo>logo.png</logo>
+    <icon>favicon.ico</icon>
+    <entry>
+        <link rel="enclosure" type="image/jpeg" href="cover.jpg"/>
+        <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><img src="inline.jpg"/></div></content>
+    </entry>
+    <entry>
+        <summary type="html">&lt;p&gt;&lt;img src="summary.jpg" width="200" height="100"&gt;&lt;/p&gt;</summary>
+    </entry>
+</feed>
+        "#;
+
+        let mut iter = atom::Iter::new(input);
+        let Some(atom::Elem::Feed(feed_iter)) = iter.next() else {
+            panic!();
+        };
+
+        let image = feed_image(feed_iter.clone()).unwrap();
+        assert_eq!(Source::Logo, image.source());
+        assert_eq!(
+            "https://example.com/blog/logo.png",
+            image.resolve("https://example.com/feed.xml")
+        );
+
+        let mut images = feed_iter.filter_map(|elem| match elem {
+            atom::FeedElem::Entry(entry) => entry_image(entry),
+            _ => None,
+        });
+
+        let image = images.next().unwrap();
+        assert_eq!("cover.jpg", image.url());
+        assert_eq!(Source::EnclosureLink, image.source());
+
+        let image = images.next().unwrap();
+        assert_eq!("summary.jpg", image.url());
+        assert_eq!(Source::Content, image.source());
+        assert_eq!((Some(200), Some(100)), (image.width(), image.height()));
+        assert_eq!(
+            "https://example.com/blog/summary.jpg",
+            image.resolve("https://example.com/feed.xml")
+        );
+    }
+}
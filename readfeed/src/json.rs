@@ -0,0 +1,357 @@
+//! Minimal reader for [JSON][json] documents.
+//!
+//! Only enough is implemented to find members in a [JSON Feed][json_feed]
+//! document. Values are returned as borrowed slices of the input.
+//!
+//! [json]: https://www.rfc-editor.org/rfc/rfc8259
+//! [json_feed]: https://www.jsonfeed.org/version/1.1/
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::str::Chars;
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skips over a string and returns the raw string including the quotes.
+    fn skip_string(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        if self.peek() != Some(b'"') {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(&self.input[start..self.pos]);
+                }
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// Skips over a member name and the following colon.
+    fn skip_member_name(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        self.skip_string()?;
+        self.eat(b':').then_some(())
+    }
+
+    /// Skips over a value and returns the raw value.
+    ///
+    /// Nested objects and arrays are tracked with a stack instead of recursion
+    /// so deeply nested input cannot overflow the call stack.
+    fn skip_value(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let mut closes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let value_start = self.pos;
+            match self.peek()? {
+                b'"' => {
+                    self.skip_string()?;
+                }
+                open @ (b'{' | b'[') => {
+                    let close = if open == b'{' { b'}' } else { b']' };
+                    self.pos += 1;
+                    if !self.eat(close) {
+                        closes.push(close);
+                        if open == b'{' {
+                            self.skip_member_name()?;
+                        }
+                        continue;
+                    }
+                }
+                _ => {
+                    while let Some(b) = self.peek() {
+                        if matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r') {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                    if self.pos == value_start {
+                        return None;
+                    }
+                }
+            }
+
+            // Close the finished containers and move to the next nested value
+            loop {
+                let Some(&close) = closes.last() else {
+                    return Some(&self.input[start..self.pos]);
+                };
+                if self.eat(close) {
+                    closes.pop();
+                    continue;
+                }
+                if !self.eat(b',') {
+                    return None;
+                }
+                if close == b'}' {
+                    self.skip_member_name()?;
+                }
+                break;
+            }
+        }
+    }
+}
+
+/// Iterator over the members of an object.
+#[derive(Debug)]
+pub(crate) struct Members<'a> {
+    input: &'a str,
+    pos: usize,
+    is_done: bool,
+}
+
+impl<'a> Iterator for Members<'a> {
+    type Item = (Cow<'a, str>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        let mut parser = Parser {
+            input: self.input,
+            pos: self.pos,
+        };
+        let member = (|| {
+            parser.skip_whitespace();
+            let name = string(parser.skip_string()?)?;
+            if !parser.eat(b':') {
+                return None;
+            }
+            let value = parser.skip_value()?;
+            if !parser.eat(b',') {
+                self.is_done = true;
+            }
+            Some((name, value))
+        })();
+
+        self.pos = parser.pos;
+        if member.is_none() {
+            self.is_done = true;
+        }
+        member
+    }
+}
+
+/// Iterator over the elements of an array.
+#[derive(Debug)]
+pub(crate) struct Elements<'a> {
+    input: &'a str,
+    pos: usize,
+    is_done: bool,
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        let mut parser = Parser {
+            input: self.input,
+            pos: self.pos,
+        };
+        let value = parser.skip_value();
+        if value.is_none() || !parser.eat(b',') {
+            self.is_done = true;
+        }
+        self.pos = parser.pos;
+        value
+    }
+}
+
+/// Returns the members of an object value.
+#[must_use]
+pub(crate) fn members(value: &str) -> Option<Members<'_>> {
+    let mut parser = Parser::new(value);
+    if !parser.eat(b'{') {
+        return None;
+    }
+    let is_done = parser.eat(b'}');
+    Some(Members {
+        input: value,
+        pos: parser.pos,
+        is_done,
+    })
+}
+
+/// Returns the elements of an array value.
+#[must_use]
+pub(crate) fn elements(value: &str) -> Option<Elements<'_>> {
+    let mut parser = Parser::new(value);
+    if !parser.eat(b'[') {
+        return None;
+    }
+    let is_done = parser.eat(b']');
+    Some(Elements {
+        input: value,
+        pos: parser.pos,
+        is_done,
+    })
+}
+
+/// Parses exactly four hexadecimal digits.
+fn hex_code_unit(chars: &mut Chars<'_>) -> Option<u32> {
+    let digits = chars.as_str().get(..4)?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    *chars = chars.as_str()[4..].chars();
+    Some(value)
+}
+
+/// Returns the decoded content of a string value.
+#[must_use]
+pub(crate) fn string(value: &str) -> Option<Cow<'_, str>> {
+    let value = value.trim();
+    let content = value.strip_prefix('"')?.strip_suffix('"')?;
+    if !content.contains('\\') {
+        return Some(Cow::Borrowed(content));
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            output.push(ch);
+            continue;
+        }
+        let ch = match chars.next()? {
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = hex_code_unit(&mut chars)?;
+                if (0xD800..0xDC00).contains(&high) {
+                    if chars.next() != Some('\\') || chars.next() != Some('u') {
+                        return None;
+                    }
+                    let low = hex_code_unit(&mut chars)?
+                        .checked_sub(0xDC00)
+                        .filter(|low| *low <= 0x3FF)?;
+                    char::from_u32(0x10000 + ((high - 0xD800) << 10) + low)?
+                } else {
+                    char::from_u32(high)?
+                }
+            }
+            ch => ch,
+        };
+        output.push(ch);
+    }
+
+    Some(Cow::Owned(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{string::String, vec::Vec};
+
+    #[test]
+    fn read_members() {
+        let input = r#" {
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Lorem \"ipsum\" \u00e9 \ud83d\ude00",
+            "empty": {},
+            "hubs": [ { "type": "WebSub", "url": "https://example.com/hub" }, [1, 2], null ],
+            "count": -1.5e3
+        } "#;
+
+        let members = members(input).unwrap().collect::<Vec<_>>();
+        assert_eq!(5, members.len());
+        assert_eq!("version", members[0].0);
+        assert_eq!(
+            Some("https://jsonfeed.org/version/1.1"),
+            string(members[0].1).as_deref()
+        );
+        assert_eq!(
+            Some("Lorem \"ipsum\" \u{e9} \u{1f600}"),
+            string(members[1].1).as_deref()
+        );
+        assert_eq!(0, super::members(members[2].1).unwrap().count());
+        assert_eq!("-1.5e3", members[4].1);
+
+        let hubs = elements(members[3].1).unwrap().collect::<Vec<_>>();
+        assert_eq!(3, hubs.len());
+        assert_eq!("null", hubs[2]);
+        let hub = super::members(hubs[0]).unwrap().collect::<Vec<_>>();
+        assert_eq!("url", hub[1].0);
+        assert_eq!(Some("https://example.com/hub"), string(hub[1].1).as_deref());
+
+        assert!(super::members("[]").is_none());
+        assert_eq!(1, super::members(r#"{"a": 1, "b": }"#).unwrap().count());
+    }
+
+    #[test]
+    fn skip_deeply_nested_value() {
+        let depth = 1_000_000;
+        let mut input = String::from(r#"{"hubs": "#);
+        input.extend(core::iter::repeat('[').take(depth));
+        input.extend(core::iter::repeat(']').take(depth));
+        input.push_str(r#", "title": "Lorem"}"#);
+
+        let members = members(&input).unwrap().collect::<Vec<_>>();
+        assert_eq!(2, members.len());
+        assert_eq!("title", members[1].0);
+
+        let input = r#"{"a": [[{"b": [1, {}]}], 2], "c": 3}"#;
+        let members = super::members(input).unwrap().collect::<Vec<_>>();
+        assert_eq!(r#"[[{"b": [1, {}]}], 2]"#, members[0].1);
+        assert_eq!("3", members[1].1);
+
+        assert_eq!(0, super::members(r#"{"a": [[1, 2}"#).unwrap().count());
+        assert_eq!(0, super::members(r#"{"a": {"b" 1}}"#).unwrap().count());
+    }
+
+    #[test]
+    fn decode_unicode_escapes() {
+        assert_eq!(Some("A"), string(r#""\u0041""#).as_deref());
+        assert_eq!(None, string(r#""\u+041""#));
+        assert_eq!(None, string(r#""\u00g1""#));
+        assert_eq!(None, string(r#""\u004""#));
+        assert_eq!(Some("\u{1f600}"), string(r#""\ud83d\ude00""#).as_deref());
+        // The low surrogate must be in the range DC00 to DFFF
+        assert_eq!(None, string(r#""\ud83d\ue000""#));
+        assert_eq!(None, string(r#""\ud83d\u0041""#));
+        assert_eq!(None, string(r#""\ud83d""#));
+    }
+}
@@ -0,0 +1,206 @@
+//! Language tags of elements.
+//!
+//! The language of an element is the nearest `xml:lang` attribute on the
+//! element or its ancestors. In RSS documents, the channel's `language`
+//! element is used if there is no `xml:lang` attribute. Finally, the
+//! `Content-Language` given to the iterator (e.g. from the HTTP response) is
+//! used.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//!
+//! let input = r#"
+//! <rss>
+//!     <channel>
+//!         <language>en-us</language>
+//!         <item xml:lang="fr_ca">
+//!             <title>Bonjour</title>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let mut iter = rss::Iter::new(input).with_content_language("de");
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ChannelElem::Language(language)) = channel_iter.next() {
+//!     assert_eq!("en-US", language.lang().unwrap().to_string());
+//! } else {
+//!     panic!();
+//! }
+//!
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//! if let Some(ItemElem::Title(title)) = item_iter.next() {
+//!     assert_eq!("fr-CA", title.lang().unwrap().to_string());
+//! } else {
+//!     panic!();
+//! }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::ToString};
+use core::fmt::{self, Write};
+
+/// A [BCP 47][bcp47] language tag.
+///
+/// The tag is kept as written in the document. It is normalized by
+/// [`Language::normalized()`] and when formatted with
+/// [`Display`][fmt::Display]: underscores are replaced with hyphens and
+/// subtags use the conventional case (e.g. `zh-Hant-TW`).
+///
+/// [bcp47]: https://www.rfc-editor.org/info/bcp47
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Language<'a> {
+    value: &'a str,
+}
+
+impl<'a> Language<'a> {
+    /// Creates a language tag from a value.
+    ///
+    /// Leading and trailing whitespace is ignored. Returns `None` if the value
+    /// is empty.
+    #[must_use]
+    pub fn new(value: &'a str) -> Option<Self> {
+        let value = value.trim();
+        (!value.is_empty()).then_some(Self { value })
+    }
+
+    /// The tag as written in the document.
+    ///
+    /// See [`Language::normalized()`] for the normalized tag.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        self.value
+    }
+
+    /// The normalized tag (e.g. `en-US` for `en_us`).
+    ///
+    /// The tag is borrowed if it is already normalized.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn normalized(&self) -> Cow<'a, str> {
+        let normalized = self.to_string();
+        if normalized == self.value {
+            Cow::Borrowed(self.value)
+        } else {
+            Cow::Owned(normalized)
+        }
+    }
+
+    /// The subtags of the tag.
+    pub fn subtags(&self) -> impl Iterator<Item = &'a str> {
+        self.value.split(['-', '_'])
+    }
+
+    /// The primary language subtag (e.g. `en` for `en-US`).
+    #[must_use]
+    pub fn primary(&self) -> &'a str {
+        self.subtags().next().unwrap_or_default()
+    }
+
+    /// Returns true if the tag is well-formed.
+    ///
+    /// Every subtag must be 1 to 8 ASCII alphanumeric characters and the
+    /// primary subtag must be alphabetic.
+    #[must_use]
+    pub fn is_well_formed(&self) -> bool {
+        self.primary().bytes().all(|b| b.is_ascii_alphabetic())
+            && self.subtags().all(|subtag| {
+                (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+            })
+    }
+
+    /// Returns true if the tags are equal after normalization.
+    #[must_use]
+    pub fn matches(&self, other: &Language<'_>) -> bool {
+        self.subtags()
+            .map(Some)
+            .chain(core::iter::once(None))
+            .zip(other.subtags().map(Some).chain(core::iter::once(None)))
+            .all(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (None, None) => true,
+                _ => false,
+            })
+    }
+}
+
+impl<'a> fmt::Display for Language<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut is_extension = false;
+        for (index, subtag) in self.subtags().enumerate() {
+            if index > 0 {
+                f.write_char('-')?;
+            }
+            // The number of leading characters which are uppercase
+            let upper = if index == 0 || is_extension {
+                0
+            } else if subtag.len() == 1 {
+                is_extension = true;
+                0
+            } else if subtag.len() == 2 {
+                2
+            } else if subtag.len() == 4 && subtag.bytes().all(|b| b.is_ascii_alphabetic()) {
+                1
+            } else {
+                0
+            };
+            for (i, ch) in subtag.chars().enumerate() {
+                f.write_char(if i < upper {
+                    ch.to_ascii_uppercase()
+                } else {
+                    ch.to_ascii_lowercase()
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        for (input, expected) in [
+            ("en", "en"),
+            ("EN-us", "en-US"),
+            (" fr_ca ", "fr-CA"),
+            ("zh-hant-tw", "zh-Hant-TW"),
+            ("es-419", "es-419"),
+            ("de-CH-x-PHONEBK", "de-CH-x-phonebk"),
+            ("en-a-bbb-x-AB", "en-a-bbb-x-ab"),
+        ] {
+            let lang = Language::new(input).unwrap();
+            assert!(lang.is_well_formed(), "{input}");
+            assert_eq!(expected, lang.to_string(), "{input}");
+            assert_eq!(expected, lang.normalized(), "{input}");
+        }
+
+        assert_eq!(None, Language::new("  "));
+        assert!(!Language::new("english (us)").unwrap().is_well_formed());
+        assert!(!Language::new("1-en").unwrap().is_well_formed());
+
+        assert!(matches!(
+            Language::new("en-US").unwrap().normalized(),
+            Cow::Borrowed("en-US")
+        ));
+
+        let lang = Language::new("en_us").unwrap();
+        assert_eq!("en", lang.primary());
+        assert!(lang.matches(&Language::new("EN-US").unwrap()));
+        assert!(!lang.matches(&Language::new("en").unwrap()));
+    }
+}
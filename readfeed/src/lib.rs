@@ -162,6 +162,11 @@
     unused_qualifications
 )]
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std as alloc;
+
 use maybe_xml::token::{
     prop::{AttributeValue, Attributes, TagName},
     EmptyElementTag, StartTag,
@@ -193,9 +198,38 @@ pub fn detect_type(input: &str) -> Ty {
 }
 
 pub mod atom;
+pub mod date;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod diff;
+pub mod ext;
 pub mod html;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod identity;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod image;
+#[cfg(feature = "alloc")]
+mod json;
+pub mod lang;
 pub mod opml;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod planet;
 pub mod rss;
+#[cfg(feature = "html-sanitize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+pub mod sanitize;
+pub mod schedule;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod text;
+pub mod uri;
+pub mod value;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod websub;
 pub mod xml;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -225,22 +259,27 @@ impl<'a> Tag<'a> {
 
     #[must_use]
     fn find_attribute(&self, needle: &str) -> Option<AttributeValue<'a>> {
-        let mut pos = 0;
-        if let Some(attrs) = self.attributes() {
-            loop {
-                if let Some(attribute) = attrs.parse(pos) {
-                    let name = attribute.name().as_str();
-                    if name.eq_ignore_ascii_case(needle) {
-                        return attribute.value();
-                    }
-                    pos += attribute.len();
-                } else {
-                    return None;
+        find_attribute(self.attributes(), needle)
+    }
+}
+
+#[must_use]
+fn find_attribute<'a>(attrs: Option<Attributes<'a>>, needle: &str) -> Option<AttributeValue<'a>> {
+    let mut pos = 0;
+    if let Some(attrs) = attrs {
+        loop {
+            if let Some(attribute) = attrs.parse(pos) {
+                let name = attribute.name().as_str();
+                if name.eq_ignore_ascii_case(needle) {
+                    return attribute.value();
                 }
+                pos += attribute.len();
+            } else {
+                return None;
             }
-        } else {
-            None
         }
+    } else {
+        None
     }
 }
 
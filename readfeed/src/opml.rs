@@ -64,7 +64,10 @@ use maybe_xml::{
     Reader,
 };
 
-use crate::{xml, Tag};
+use crate::{
+    value::{self, ParseError},
+    xml, Tag,
+};
 
 macro_rules! content_elem {
   ($name:ident $(,)?) => {
@@ -203,6 +206,57 @@ content_elem!(
     WindowRight
 );
 
+impl<'a> ExpansionState<'a> {
+    /// Parses the content as the comma-separated line numbers of the expanded
+    /// outlines.
+    pub fn values(&self) -> impl Iterator<Item = Result<u32, ParseError>> + 'a {
+        self.content
+            .split(',')
+            .filter(|v| !v.trim().is_empty())
+            .map(value::parse_u32)
+    }
+}
+
+impl<'a> VertScrollState<'a> {
+    /// Parses the content as the line number of the top visible outline.
+    #[inline]
+    pub fn value(&self) -> Result<u32, ParseError> {
+        value::parse_u32(self.content)
+    }
+}
+
+impl<'a> WindowTop<'a> {
+    /// Parses the content as the pixel location of the top edge of the window.
+    #[inline]
+    pub fn value(&self) -> Result<i32, ParseError> {
+        value::parse_i32(self.content)
+    }
+}
+
+impl<'a> WindowLeft<'a> {
+    /// Parses the content as the pixel location of the left edge of the window.
+    #[inline]
+    pub fn value(&self) -> Result<i32, ParseError> {
+        value::parse_i32(self.content)
+    }
+}
+
+impl<'a> WindowBottom<'a> {
+    /// Parses the content as the pixel location of the bottom edge of the window.
+    #[inline]
+    pub fn value(&self) -> Result<i32, ParseError> {
+        value::parse_i32(self.content)
+    }
+}
+
+impl<'a> WindowRight<'a> {
+    /// Parses the content as the pixel location of the right edge of the window.
+    #[inline]
+    pub fn value(&self) -> Result<i32, ParseError> {
+        value::parse_i32(self.content)
+    }
+}
+
 impl_date_construct!(DateCreated, DateModified);
 
 #[derive(Debug, PartialEq, Eq, Hash)]
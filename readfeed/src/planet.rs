@@ -0,0 +1,1091 @@
+//! Aggregates the items and entries of many feeds into one feed.
+//!
+//! A [`Planet`] collects the entries of parsed RSS channels and Atom feeds.
+//! Entries are deduplicated by their [`Identity`] and sorted by date, newest
+//! first. Each entry keeps the [`Source`] feed it came from, which is written
+//! as an Atom `source` element or an RSS `source` element.
+//!
+//! Identities which are not absolute URIs (e.g. a `guid` of `1` or a hash)
+//! only deduplicate entries from the same feed.
+//!
+//! If the `html-sanitize` feature is enabled, summaries and content are
+//! sanitized with a `Policy` when they are added. Otherwise, markup from the
+//! source feeds is written as is.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::{atom, planet::Planet, rss};
+//!
+//! let blog_a = "
+//! <rss>
+//!     <channel>
+//!         <title>Blog A</title>
+//!         <item>
+//!             <title>Hello</title>
+//!             <link>https://a.example.com/hello</link>
+//!             <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! ";
+//! let blog_b = r#"
+//! <feed xmlns="http://www.w3.org/2005/Atom">
+//!     <title>Blog B</title>
+//!     <id>https://b.example.com/</id>
+//!     <entry>
+//!         <title>World</title>
+//!         <id>https://b.example.com/world</id>
+//!         <updated>2024-01-02T00:00:00Z</updated>
+//!     </entry>
+//! </feed>
+//! "#;
+//!
+//! let mut planet = Planet::new("Planet", "https://planet.example.com/");
+//! for elem in rss::Iter::new(blog_a) {
+//!     if let rss::Elem::Rss(rss) = elem {
+//!         for elem in rss {
+//!             if let rss::RssElem::Channel(channel) = elem {
+//!                 planet.add_channel(channel, "https://a.example.com/feed.xml");
+//!             }
+//!         }
+//!     }
+//! }
+//! for elem in atom::Iter::new(blog_b) {
+//!     if let atom::Elem::Feed(feed) = elem {
+//!         planet.add_feed(feed, "https://b.example.com/atom.xml");
+//!     }
+//! }
+//!
+//! let entries = planet.entries();
+//! assert_eq!(Some("World"), entries[0].0.title());
+//! assert_eq!(Some("Blog B"), entries[0].1.title());
+//! assert_eq!(Some("Hello"), entries[1].0.title());
+//!
+//! let mut output = String::new();
+//! planet.write_atom(&mut output)?;
+//! assert!(output.contains("<source><id>https://b.example.com/</id><title>Blog B</title>"));
+//! # Ok::<(), core::fmt::Error>(())
+//! ```
+
+use alloc::{borrow::Cow, collections::BTreeMap, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt};
+
+#[cfg(not(feature = "html-sanitize"))]
+use crate::atom::ContentValue;
+#[cfg(feature = "html-sanitize")]
+use crate::sanitize::Policy;
+use crate::{
+    atom,
+    date::DateTime,
+    ext::{content, dc},
+    identity::{decode_non_empty, Fields, Identity},
+    rss, uri,
+    xml::Writer,
+};
+
+/// The feed which an entry came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Source<'a> {
+    url: &'a str,
+    id: Option<Cow<'a, str>>,
+    title: Option<String>,
+    link: Option<String>,
+    updated: Option<DateTime>,
+}
+
+impl<'a> Source<'a> {
+    #[must_use]
+    fn new(url: &'a str) -> Self {
+        Self {
+            url,
+            id: None,
+            title: None,
+            link: None,
+            updated: None,
+        }
+    }
+
+    /// The URL the feed was fetched from.
+    #[inline]
+    #[must_use]
+    pub const fn url(&self) -> &'a str {
+        self.url
+    }
+
+    /// The `id` of an Atom feed.
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// The `title` of the feed as plain text.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The `link` of an RSS channel or the alternate `link` of an Atom feed,
+    /// resolved against the feed URL.
+    #[must_use]
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// The `lastBuildDate` (or `pubDate`) of an RSS channel or the `updated`
+    /// date of an Atom feed.
+    #[inline]
+    #[must_use]
+    pub const fn updated(&self) -> Option<DateTime> {
+        self.updated
+    }
+}
+
+/// An entry in an aggregated feed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry<'a> {
+    identity: Identity<'a>,
+    title: Option<String>,
+    link: Option<String>,
+    base: Option<String>,
+    author: Option<String>,
+    summary: Option<String>,
+    content: Option<String>,
+    published: Option<DateTime>,
+    updated: Option<DateTime>,
+    source: usize,
+}
+
+impl<'a> Entry<'a> {
+    #[must_use]
+    fn new(identity: Identity<'a>, source: usize) -> Self {
+        Self {
+            identity,
+            title: None,
+            link: None,
+            base: None,
+            author: None,
+            summary: None,
+            content: None,
+            published: None,
+            updated: None,
+            source,
+        }
+    }
+
+    /// The identity of the item or entry.
+    #[inline]
+    #[must_use]
+    pub const fn identity(&self) -> &Identity<'a> {
+        &self.identity
+    }
+
+    /// The title as plain text.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The link resolved against the base URI.
+    #[must_use]
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// The name of the author.
+    ///
+    /// For RSS items, the `dc:creator` or `author` element is used.
+    #[must_use]
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// The summary as HTML, which is sanitized if the `html-sanitize` feature
+    /// is enabled.
+    ///
+    /// For RSS items, the `description` element is used.
+    #[must_use]
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// The content as HTML, which is sanitized if the `html-sanitize` feature
+    /// is enabled.
+    ///
+    /// For RSS items, the `content:encoded` element is used. Out-of-line, XML,
+    /// and binary Atom content is not included.
+    #[must_use]
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
+    }
+
+    /// The `pubDate` of an RSS item or the `published` date of an Atom entry.
+    ///
+    /// For RSS items without a `pubDate`, the `dc:date` element is used.
+    #[inline]
+    #[must_use]
+    pub const fn published(&self) -> Option<DateTime> {
+        self.published
+    }
+
+    /// The `updated` date of an Atom entry.
+    #[inline]
+    #[must_use]
+    pub const fn updated(&self) -> Option<DateTime> {
+        self.updated
+    }
+
+    /// The date used to sort entries, which is the published date or else the
+    /// updated date.
+    #[inline]
+    #[must_use]
+    pub fn date(&self) -> Option<DateTime> {
+        self.published.or(self.updated)
+    }
+
+    /// The `id` written for the entry.
+    ///
+    /// Identities which are not absolute URIs are appended as a fragment to
+    /// the URL of the source feed.
+    #[must_use]
+    fn id(&self, source: &Source<'_>) -> Cow<'_, str> {
+        let key = self.identity.key();
+        if uri::scheme(key).is_some() {
+            Cow::Borrowed(key)
+        } else {
+            Cow::Owned(alloc::format!("{}#{key}", source.url))
+        }
+    }
+}
+
+/// Compares dates with the newest first and missing dates last.
+#[must_use]
+fn cmp_newest_first(a: Option<DateTime>, b: Option<DateTime>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.unix_timestamp().cmp(&a.unix_timestamp()),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// The inline content of an Atom `content` element as HTML.
+#[cfg(not(feature = "html-sanitize"))]
+#[must_use]
+fn content_html(content: &atom::Content<'_>) -> Option<String> {
+    match content.to_content_value().ok()? {
+        ContentValue::Text(value) => Some(value.to_unsanitized_html()),
+        ContentValue::MediaText { media_type, text } => {
+            let essence = media_type.split(';').next().unwrap_or_default().trim();
+            Some(if essence.eq_ignore_ascii_case("text/html") {
+                text
+            } else {
+                crate::text::escape_html(&text)
+            })
+        }
+        ContentValue::Xml { .. } | ContentValue::Binary { .. } | ContentValue::OutOfLine { .. } => {
+            None
+        }
+    }
+}
+
+/// Returns the value if it is not only whitespace.
+#[must_use]
+fn non_empty(value: String) -> Option<String> {
+    (!value.trim().is_empty()).then_some(value)
+}
+
+/// The name of the first person in an Atom `author` element.
+#[must_use]
+fn person_name(person: atom::PersonIter<'_>) -> Option<String> {
+    person.into_iter().find_map(|elem| match elem {
+        atom::PersonElem::Name(name) => decode_non_empty(name.content()).map(Cow::into_owned),
+        _ => None,
+    })
+}
+
+/// Aggregates the items and entries of many feeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Planet<'a> {
+    title: &'a str,
+    link: &'a str,
+    id: Option<&'a str>,
+    description: Option<&'a str>,
+    limit: Option<usize>,
+    #[cfg(feature = "html-sanitize")]
+    policy: Policy,
+    sources: Vec<Source<'a>>,
+    entries: Vec<Entry<'a>>,
+    index: BTreeMap<(Option<usize>, Identity<'a>), usize>,
+}
+
+impl<'a> Planet<'a> {
+    /// Creates an aggregated feed with a title and a link to the planet page.
+    #[must_use]
+    pub fn new(title: &'a str, link: &'a str) -> Self {
+        Self {
+            title,
+            link,
+            id: None,
+            description: None,
+            limit: None,
+            #[cfg(feature = "html-sanitize")]
+            policy: Policy::new(),
+            sources: Vec::new(),
+            entries: Vec::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the `id` of the Atom feed. The link is used by default.
+    #[inline]
+    #[must_use]
+    pub const fn with_id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the `description` of the RSS channel. The title is used by
+    /// default.
+    #[inline]
+    #[must_use]
+    pub const fn with_description(mut self, description: &'a str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Sets the maximum number of entries which are returned and written.
+    #[inline]
+    #[must_use]
+    pub const fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the policy used to sanitize summaries and content which are
+    /// added afterwards. The default policy is used by default.
+    #[cfg(feature = "html-sanitize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+    #[inline]
+    #[must_use]
+    pub const fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The feeds which were added.
+    #[inline]
+    #[must_use]
+    pub fn sources(&self) -> &[Source<'a>] {
+        &self.sources
+    }
+
+    /// Adds the entries of an Atom feed which was fetched from the URL.
+    ///
+    /// Relative URLs are resolved against the URL.
+    pub fn add_feed(&mut self, feed: atom::FeedIter<'a>, url: &'a str) {
+        let source_index = self.sources.len();
+        let mut source = Source::new(url);
+        let mut feed_author = None;
+        let mut entries = Vec::new();
+        for elem in feed {
+            match elem {
+                atom::FeedElem::Id(id) if source.id.is_none() => {
+                    source.id = decode_non_empty(id.content());
+                }
+                atom::FeedElem::Title(title) if source.title.is_none() => {
+                    source.title = Some(title.to_plain_text()).filter(|title| !title.is_empty());
+                }
+                atom::FeedElem::Link(link)
+                    if source.link.is_none()
+                        && link.rel().map_or(true, |rel| {
+                            rel.as_str().trim().eq_ignore_ascii_case("alternate")
+                        }) =>
+                {
+                    source.link = link.resolve_href(url);
+                }
+                atom::FeedElem::Updated(updated) if source.updated.is_none() => {
+                    source.updated = updated.to_date_time();
+                }
+                atom::FeedElem::Author(person) if feed_author.is_none() => {
+                    feed_author = person_name(person);
+                }
+                atom::FeedElem::Entry(entry) => {
+                    entries.push(self.entry_from_atom(entry, url, source_index));
+                }
+                _ => {}
+            }
+        }
+        self.sources.push(source);
+        for mut entry in entries {
+            if entry.author.is_none() {
+                entry.author.clone_from(&feed_author);
+            }
+            self.insert(entry);
+        }
+    }
+
+    /// Adds the items of an RSS channel which was fetched from the URL.
+    ///
+    /// Relative URLs are resolved against the URL.
+    pub fn add_channel(&mut self, channel: rss::ChannelIter<'a>, url: &'a str) {
+        let source_index = self.sources.len();
+        let mut source = Source::new(url);
+        let mut pub_date = None;
+        let mut entries = Vec::new();
+        for elem in channel {
+            match elem {
+                rss::ChannelElem::Title(title) if source.title.is_none() => {
+                    source.title = decode_non_empty(title.content()).map(Cow::into_owned);
+                }
+                rss::ChannelElem::Link(link) if source.link.is_none() => {
+                    source.link =
+                        decode_non_empty(link.content()).map(|link| uri::resolve(url, &link));
+                }
+                rss::ChannelElem::LastBuildDate(date) if source.updated.is_none() => {
+                    source.updated = date.to_date_time();
+                }
+                rss::ChannelElem::PubDate(date) if pub_date.is_none() => {
+                    pub_date = date.to_date_time();
+                }
+                rss::ChannelElem::Item(item) => {
+                    entries.push(self.entry_from_rss(item, url, source_index));
+                }
+                _ => {}
+            }
+        }
+        if source.updated.is_none() {
+            source.updated = pub_date;
+        }
+        self.sources.push(source);
+        for entry in entries {
+            self.insert(entry);
+        }
+    }
+
+    #[must_use]
+    fn entry_from_atom(&self, entry: atom::EntryIter<'a>, url: &str, source: usize) -> Entry<'a> {
+        let base = entry.base().to_uri(url);
+        let fields = Fields::from_entry(entry.clone());
+        let mut value = Entry::new(fields.identity(), source);
+        value.link = fields.link.map(|link| uri::resolve(&base, &link));
+        value.published = fields.published.and_then(|date| date.date_time);
+        value.updated = fields.updated.and_then(|date| date.date_time);
+        for elem in entry {
+            match elem {
+                atom::EntryElem::Title(title) if value.title.is_none() => {
+                    value.title = Some(title.to_plain_text()).filter(|title| !title.is_empty());
+                }
+                atom::EntryElem::Author(person) if value.author.is_none() => {
+                    value.author = person_name(person);
+                }
+                atom::EntryElem::Summary(summary) if value.summary.is_none() => {
+                    #[cfg(feature = "html-sanitize")]
+                    let html = summary.to_sanitized_html(url, &self.policy);
+                    #[cfg(not(feature = "html-sanitize"))]
+                    let html = summary.to_text_construct().to_unsanitized_html();
+                    value.summary = non_empty(html);
+                }
+                atom::EntryElem::Content(content) if value.content.is_none() => {
+                    #[cfg(feature = "html-sanitize")]
+                    let html = content.to_sanitized_html(url, &self.policy);
+                    #[cfg(not(feature = "html-sanitize"))]
+                    let html = content_html(&content);
+                    value.content = html.and_then(non_empty);
+                }
+                _ => {}
+            }
+        }
+        value.base = Some(base);
+        value
+    }
+
+    #[must_use]
+    fn entry_from_rss(
+        &self,
+        item: rss::ChannelItemIter<'a>,
+        url: &str,
+        source: usize,
+    ) -> Entry<'a> {
+        let base = item.base().to_uri(url);
+        let fields = Fields::from_item(item.clone());
+        let mut value = Entry::new(fields.identity(), source);
+        value.title = fields.title.map(Cow::into_owned);
+        value.link = fields.link.map(|link| uri::resolve(&base, &link));
+        value.published = fields.published.and_then(|date| date.date_time);
+        let mut dc_creator = None;
+        for elem in item {
+            match elem {
+                rss::ItemElem::Author(author) if value.author.is_none() => {
+                    value.author = decode_non_empty(author.content()).map(Cow::into_owned);
+                }
+                rss::ItemElem::Dc(dc::Elem::Creator(creator)) if dc_creator.is_none() => {
+                    dc_creator = decode_non_empty(creator.content()).map(Cow::into_owned);
+                }
+                rss::ItemElem::Description(desc) if value.summary.is_none() => {
+                    #[cfg(feature = "html-sanitize")]
+                    let html = desc.to_sanitized_html(url, &self.policy);
+                    #[cfg(not(feature = "html-sanitize"))]
+                    let html = crate::xml::decode(desc.content()).into_owned();
+                    value.summary = non_empty(html);
+                }
+                rss::ItemElem::Content(content::Elem::Encoded(encoded))
+                    if value.content.is_none() =>
+                {
+                    #[cfg(feature = "html-sanitize")]
+                    let html = encoded.to_sanitized_html(url, &self.policy);
+                    #[cfg(not(feature = "html-sanitize"))]
+                    let html = encoded.html().into_owned();
+                    value.content = non_empty(html);
+                }
+                _ => {}
+            }
+        }
+        if dc_creator.is_some() {
+            value.author = dc_creator;
+        }
+        value.base = Some(base);
+        value
+    }
+
+    /// Adds an entry or replaces a duplicate entry if the new entry was updated
+    /// more recently.
+    fn insert(&mut self, entry: Entry<'a>) {
+        let scope = uri::scheme(entry.identity.key())
+            .is_none()
+            .then_some(entry.source);
+        let key = (scope, entry.identity.clone());
+        if let Some(&index) = self.index.get(&key) {
+            let existing = &self.entries[index];
+            let updated = |entry: &Entry<'_>| entry.updated.or(entry.published);
+            if cmp_newest_first(updated(&entry), updated(existing)) == Ordering::Less {
+                self.entries[index] = entry;
+            }
+        } else {
+            self.index.insert(key, self.entries.len());
+            self.entries.push(entry);
+        }
+    }
+
+    /// The deduplicated entries with their source, sorted by date with the
+    /// newest first.
+    ///
+    /// Entries without a date are last. Entries with the same date are in the
+    /// order they were added.
+    #[must_use]
+    pub fn entries(&self) -> Vec<(&Entry<'a>, &Source<'a>)> {
+        let mut entries = self
+            .entries
+            .iter()
+            .map(|entry| (entry, &self.sources[entry.source]))
+            .collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| cmp_newest_first(a.date(), b.date()));
+        if let Some(limit) = self.limit {
+            entries.truncate(limit);
+        }
+        entries
+    }
+
+    /// The date of the most recently updated entry, or else of the most
+    /// recently updated feed, or else the Unix epoch.
+    #[must_use]
+    fn updated(&self) -> DateTime {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.updated.or(entry.published))
+            .chain(self.sources.iter().filter_map(|source| source.updated))
+            .min_by(|a, b| cmp_newest_first(Some(*a), Some(*b)))
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+
+    /// Writes the aggregated feed as an Atom document.
+    ///
+    /// Each entry has a `source` element with the `id`, `title`, links, and
+    /// `updated` date of its feed. Entries without an author use the author of
+    /// their feed, or else the title of their feed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_atom<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let mut date = String::new();
+        let mut writer = Writer::new(w)?;
+        writer.start("feed", &[("xmlns", "http://www.w3.org/2005/Atom")])?;
+        writer.text_element("title", &[], self.title)?;
+        writer.text_element("id", &[], self.id.unwrap_or(self.link))?;
+        writer.empty("link", &[("rel", "alternate"), ("href", self.link)])?;
+        let updated = self.updated();
+        updated.write_w3cdtf(&mut date)?;
+        writer.text_element("updated", &[], &date)?;
+
+        for (entry, source) in self.entries() {
+            writer.start(
+                "entry",
+                &[("xml:base", entry.base.as_deref().unwrap_or(source.url))],
+            )?;
+            writer.text_element("id", &[], &entry.id(source))?;
+            writer.text_element("title", &[], entry.title().unwrap_or_default())?;
+            date.clear();
+            entry
+                .updated
+                .or(entry.published)
+                .or(source.updated)
+                .unwrap_or(updated)
+                .write_w3cdtf(&mut date)?;
+            writer.text_element("updated", &[], &date)?;
+            if let Some(published) = entry.published {
+                date.clear();
+                published.write_w3cdtf(&mut date)?;
+                writer.text_element("published", &[], &date)?;
+            }
+            if let Some(link) = entry.link() {
+                writer.empty("link", &[("rel", "alternate"), ("href", link)])?;
+            }
+            if let Some(author) = entry.author().or(source.title()) {
+                writer.start("author", &[])?;
+                writer.text_element("name", &[], author)?;
+                writer.end("author")?;
+            }
+            if let Some(summary) = entry.summary() {
+                writer.text_element("summary", &[("type", "html")], summary)?;
+            }
+            if let Some(content) = entry.content() {
+                writer.text_element("content", &[("type", "html")], content)?;
+            }
+
+            writer.start("source", &[])?;
+            writer.text_element("id", &[], source.id().unwrap_or(source.url))?;
+            if let Some(title) = source.title() {
+                writer.text_element("title", &[], title)?;
+            }
+            if let Some(link) = source.link() {
+                writer.empty("link", &[("rel", "alternate"), ("href", link)])?;
+            }
+            writer.empty("link", &[("rel", "self"), ("href", source.url)])?;
+            if let Some(updated) = source.updated {
+                date.clear();
+                updated.write_w3cdtf(&mut date)?;
+                writer.text_element("updated", &[], &date)?;
+            }
+            writer.end("source")?;
+
+            writer.end("entry")?;
+        }
+
+        writer.end("feed")
+    }
+
+    /// Writes the aggregated feed as an RSS 2.0 document.
+    ///
+    /// Each item has a `source` element with the title and URL of its feed. The
+    /// author is written as a `dc:creator` element and the content as a
+    /// `content:encoded` element.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_rss<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let mut date = String::new();
+        let mut writer = Writer::new(w)?;
+        writer.start(
+            "rss",
+            &[
+                ("version", "2.0"),
+                ("xmlns:content", "http://purl.org/rss/1.0/modules/content/"),
+                ("xmlns:dc", "http://purl.org/dc/elements/1.1/"),
+            ],
+        )?;
+        writer.start("channel", &[])?;
+        writer.text_element("title", &[], self.title)?;
+        writer.text_element("link", &[], self.link)?;
+        writer.text_element("description", &[], self.description.unwrap_or(self.title))?;
+        self.updated().write_rfc2822(&mut date)?;
+        writer.text_element("lastBuildDate", &[], &date)?;
+
+        for (entry, source) in self.entries() {
+            writer.start(
+                "item",
+                &[("xml:base", entry.base.as_deref().unwrap_or(source.url))],
+            )?;
+            if let Some(title) = entry.title() {
+                writer.text_element("title", &[], title)?;
+            }
+            if let Some(link) = entry.link() {
+                writer.text_element("link", &[], link)?;
+            }
+            if let Some(description) = entry.summary().or(entry.content()) {
+                writer.text_element("description", &[], description)?;
+            }
+            if let Some(content) = entry.content() {
+                writer.text_element("content:encoded", &[], content)?;
+            }
+            if let Some(author) = entry.author() {
+                writer.text_element("dc:creator", &[], author)?;
+            }
+            writer.text_element("guid", &[("isPermaLink", "false")], &entry.id(source))?;
+            if let Some(published) = entry.date() {
+                date.clear();
+                published.write_rfc2822(&mut date)?;
+                writer.text_element("pubDate", &[], &date)?;
+            }
+            writer.text_element(
+                "source",
+                &[("url", source.url)],
+                source.title().unwrap_or(source.url),
+            )?;
+            writer.end("item")?;
+        }
+
+        writer.end("channel")?;
+        writer.end("rss")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::identity::Strategy;
+
+    fn add_rss<'a>(planet: &mut Planet<'a>, input: &'a str, url: &'a str) {
+        for elem in rss::Iter::new(input) {
+            if let rss::Elem::Rss(rss) = elem {
+                for elem in rss {
+                    if let rss::RssElem::Channel(channel) = elem {
+                        planet.add_channel(channel, url);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_atom<'a>(planet: &mut Planet<'a>, input: &'a str, url: &'a str) {
+        for elem in atom::Iter::new(input) {
+            if let atom::Elem::Feed(feed) = elem {
+                planet.add_feed(feed, url);
+            }
+        }
+    }
+
+    #[test]
+    fn aggregate_feeds() {
+        let blog_a = r#"
+<rss xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <channel>
+        <title>Blog A</title>
+        <link>/</link>
+        <item>
+            <guid>1</guid>
+            <title>First</title>
+            <link>/first</link>
+            <description>&lt;p&gt;Lorem&lt;/p&gt;</description>
+            <dc:creator>Alice</dc:creator>
+            <pubDate>Mon, 01 Jan 2024 10:00:00 +0100</pubDate>
+        </item>
+        <item>
+            <guid>https://example.com/shared</guid>
+            <title>Shared (old)</title>
+            <pubDate>Tue, 02 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+        <item>
+            <title>Undated</title>
+        </item>
+    </channel>
+</rss>
+        "#;
+        let blog_b = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <title>Blog B</title>
+    <id>urn:uuid:b</id>
+    <author><name>Bob</name></author>
+    <updated>2024-01-03T00:00:00Z</updated>
+    <entry>
+        <id>1</id>
+        <title type="html">&lt;b&gt;Second&lt;/b&gt;</title>
+        <published>2024-01-01T12:00:00Z</published>
+        <content type="text">1 &lt; 2</content>
+    </entry>
+    <entry>
+        <id>https://example.com/shared</id>
+        <title>Shared</title>
+        <published>2024-01-02T00:00:00Z</published>
+        <updated>2024-01-03T00:00:00Z</updated>
+    </entry>
+</feed>
+        "#;
+
+        let mut planet = Planet::new("Planet", "https://planet.example.com/");
+        add_rss(&mut planet, blog_a, "https://a.example.com/feed");
+        add_atom(&mut planet, blog_b, "https://b.example.com/feed");
+
+        let entries = planet.entries();
+        assert_eq!(
+            vec![
+                (Some("Shared"), Some("Blog B")),
+                (Some("Second"), Some("Blog B")),
+                (Some("First"), Some("Blog A")),
+                (Some("Undated"), Some("Blog A")),
+            ],
+            entries
+                .iter()
+                .map(|(entry, source)| (entry.title(), source.title()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("https://a.example.com/first"), entries[2].0.link());
+        assert_eq!(Some("Alice"), entries[2].0.author());
+        assert_eq!(Some("Bob"), entries[1].0.author());
+
+        let mut output = String::new();
+        planet
+            .clone()
+            .with_limit(2)
+            .write_atom(&mut output)
+            .unwrap();
+        assert_eq!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+                "<feed xmlns=\"http://www.w3.org/2005/Atom\">",
+                "<title>Planet</title>",
+                "<id>https://planet.example.com/</id>",
+                "<link rel=\"alternate\" href=\"https://planet.example.com/\"/>",
+                "<updated>2024-01-03T00:00:00Z</updated>",
+                "<entry xml:base=\"https://b.example.com/feed\">",
+                "<id>https://example.com/shared</id>",
+                "<title>Shared</title>",
+                "<updated>2024-01-03T00:00:00Z</updated>",
+                "<published>2024-01-02T00:00:00Z</published>",
+                "<author><name>Bob</name></author>",
+                "<source><id>urn:uuid:b</id><title>Blog B</title>",
+                "<link rel=\"self\" href=\"https://b.example.com/feed\"/>",
+                "<updated>2024-01-03T00:00:00Z</updated></source>",
+                "</entry>",
+                "<entry xml:base=\"https://b.example.com/feed\">",
+                "<id>https://b.example.com/feed#1</id>",
+                "<title>Second</title>",
+                "<updated>2024-01-01T12:00:00Z</updated>",
+                "<published>2024-01-01T12:00:00Z</published>",
+                "<author><name>Bob</name></author>",
+                "<content type=\"html\">1 &amp;lt; 2</content>",
+                "<source><id>urn:uuid:b</id><title>Blog B</title>",
+                "<link rel=\"self\" href=\"https://b.example.com/feed\"/>",
+                "<updated>2024-01-03T00:00:00Z</updated></source>",
+                "</entry>",
+                "</feed>",
+            ),
+            output
+        );
+
+        let mut output = String::new();
+        planet.with_limit(3).write_rss(&mut output).unwrap();
+        let item = concat!(
+            "<item xml:base=\"https://a.example.com/feed\">",
+            "<title>First</title>",
+            "<link>https://a.example.com/first</link>",
+            "<description>&lt;p&gt;Lorem&lt;/p&gt;</description>",
+            "<dc:creator>Alice</dc:creator>",
+            "<guid isPermaLink=\"false\">https://a.example.com/feed#1</guid>",
+            "<pubDate>Mon, 01 Jan 2024 10:00:00 +0100</pubDate>",
+            "<source url=\"https://a.example.com/feed\">Blog A</source>",
+            "</item>",
+        );
+        assert!(output.contains(item), "{output}");
+        assert!(output.contains("<lastBuildDate>Wed, 03 Jan 2024 00:00:00 +0000</lastBuildDate>"));
+        assert_eq!(3, output.matches("<item ").count());
+    }
+
+    #[test]
+    fn replace_duplicates_across_feeds() {
+        let feed = |updated: &str, title: &str| {
+            alloc::format!(
+                r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <entry>
+        <id>https://example.com/1</id>
+        <title>{title}</title>
+        <updated>{updated}</updated>
+    </entry>
+</feed>
+                "#
+            )
+        };
+        let a = feed("2024-01-02T00:00:00Z", "A");
+        let b = feed("2024-01-01T00:00:00Z", "B");
+        let c = feed("2024-01-03T00:00:00Z", "C");
+
+        let mut planet = Planet::new("Planet", "https://planet.example.com/");
+        add_atom(&mut planet, &a, "https://a.example.com/feed");
+        add_atom(&mut planet, &b, "https://b.example.com/feed");
+
+        let entries = planet.entries();
+        assert_eq!(1, entries.len());
+        assert_eq!(Some("A"), entries[0].0.title());
+        assert_eq!("https://a.example.com/feed", entries[0].1.url());
+
+        add_atom(&mut planet, &c, "https://c.example.com/feed");
+
+        let entries = planet.entries();
+        assert_eq!(1, entries.len());
+        assert_eq!(Some("C"), entries[0].0.title());
+        assert_eq!("https://c.example.com/feed", entries[0].1.url());
+        assert_eq!(3, planet.sources().len());
+    }
+
+    #[test]
+    fn scope_identities_to_feed() {
+        let input = r#"
+<rss>
+    <channel>
+        <item>
+            <guid>1</guid>
+            <title>Lorem</title>
+        </item>
+        <item>
+            <title>Ipsum</title>
+            <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+        <item>
+            <guid>1</guid>
+            <title>Lorem (again)</title>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut planet = Planet::new("Planet", "https://planet.example.com/");
+        add_rss(&mut planet, input, "https://a.example.com/feed");
+        add_rss(&mut planet, input, "https://b.example.com/feed");
+
+        let entries = planet.entries();
+        assert_eq!(4, entries.len());
+        assert_eq!(
+            vec![
+                (Strategy::Hash, "https://a.example.com/feed"),
+                (Strategy::Hash, "https://b.example.com/feed"),
+                (Strategy::Id, "https://a.example.com/feed"),
+                (Strategy::Id, "https://b.example.com/feed"),
+            ],
+            entries
+                .iter()
+                .map(|(entry, source)| (entry.identity().strategy(), source.url()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(entries[0].0.identity(), entries[1].0.identity());
+        assert_eq!(Some("Lorem"), entries[2].0.title());
+    }
+
+    #[test]
+    fn write_rss_from_atom() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <title>Blog</title>
+    <author><name>Bob</name></author>
+    <entry xml:base="/posts/">
+        <id>1</id>
+        <title>Lorem</title>
+        <link href="lorem"/>
+        <updated>2024-01-02T00:00:00Z</updated>
+        <summary type="html">&lt;p onclick="x()"&gt;Lorem&lt;/p&gt;&lt;script&gt;x()&lt;/script&gt;</summary>
+        <content type="html">&lt;img src="1.png"&gt;</content>
+    </entry>
+</feed>
+        "#;
+
+        let mut planet = Planet::new("Planet", "https://planet.example.com/");
+        add_atom(&mut planet, input, "https://example.com/feed");
+
+        let mut output = String::new();
+        planet.write_rss(&mut output).unwrap();
+        #[cfg(feature = "html-sanitize")]
+        let html = concat!(
+            "<description>&lt;p&gt;Lorem&lt;/p&gt;</description>",
+            "<content:encoded>&lt;img src=&quot;https://example.com/posts/1.png&quot;&gt;</content:encoded>",
+        );
+        #[cfg(not(feature = "html-sanitize"))]
+        let html = concat!(
+            "<description>&lt;p onclick=&quot;x()&quot;&gt;Lorem&lt;/p&gt;&lt;script&gt;x()&lt;/script&gt;</description>",
+            "<content:encoded>&lt;img src=&quot;1.png&quot;&gt;</content:encoded>",
+        );
+        let item = alloc::format!(
+            "{}{}{}",
+            concat!(
+                "<item xml:base=\"https://example.com/posts/\">",
+                "<title>Lorem</title>",
+                "<link>https://example.com/posts/lorem</link>",
+            ),
+            html,
+            concat!(
+                "<dc:creator>Bob</dc:creator>",
+                "<guid isPermaLink=\"false\">https://example.com/feed#1</guid>",
+                "<pubDate>Tue, 02 Jan 2024 00:00:00 +0000</pubDate>",
+                "<source url=\"https://example.com/feed\">Blog</source>",
+                "</item>",
+            ),
+        );
+        assert!(output.contains(&item), "{output}");
+    }
+
+    #[test]
+    fn write_atom_without_dates() {
+        let input = "
+<rss>
+    <channel>
+        <item>
+            <guid>1</guid>
+        </item>
+    </channel>
+</rss>
+        ";
+
+        let mut planet = Planet::new("Planet", "https://planet.example.com/");
+        add_rss(&mut planet, input, "https://example.com/feed");
+
+        let mut output = String::new();
+        planet.write_atom(&mut output).unwrap();
+        assert_eq!(
+            2,
+            output
+                .matches("<updated>1970-01-01T00:00:00Z</updated>")
+                .count(),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn limit_entries() {
+        let input = "
+<rss>
+    <channel>
+        <item>
+            <guid>1</guid>
+            <pubDate>Mon, 01 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+        <item>
+            <guid>2</guid>
+            <pubDate>Wed, 03 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+        <item>
+            <guid>3</guid>
+            <pubDate>Tue, 02 Jan 2024 00:00:00 GMT</pubDate>
+        </item>
+    </channel>
+</rss>
+        ";
+
+        let mut planet = Planet::new("Planet", "https://planet.example.com/");
+        add_rss(&mut planet, input, "https://example.com/feed");
+        assert_eq!(3, planet.entries().len());
+
+        let planet = planet.with_limit(2);
+        assert_eq!(
+            vec!["2", "3"],
+            planet
+                .entries()
+                .iter()
+                .map(|(entry, _)| entry.identity().key())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, planet.with_limit(0).entries().len());
+    }
+}
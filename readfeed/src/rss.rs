@@ -67,6 +67,9 @@
 //!
 //! [rss]: https://www.rssboard.org/rss-specification
 
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
 use maybe_xml::{
     token::{
         self,
@@ -76,7 +79,18 @@ use maybe_xml::{
     Reader,
 };
 
-use crate::{xml, Tag};
+#[cfg(feature = "html-sanitize")]
+use crate::sanitize::Policy;
+use crate::{
+    atom,
+    date::DateTime,
+    ext::{self, content, dc, fh, itunes, media, podcast, sy},
+    lang::Language,
+    uri::Base,
+    value::{self, ParseError, Weekday},
+    xml::{self, Scope},
+    Tag,
+};
 
 macro_rules! content_elem {
     ($name:ident $(,)?) => {
@@ -84,6 +98,7 @@ macro_rules! content_elem {
         pub struct $name<'a> {
             tag: Tag<'a>,
             content: &'a str,
+            scope: Scope<'a>,
         }
 
         impl<'a> $name<'a> {
@@ -98,6 +113,23 @@ macro_rules! content_elem {
             pub const fn attributes(&self) -> Option<Attributes<'a>> {
                 self.tag.attributes()
             }
+
+            /// The `xml:base` values which apply to the element.
+            #[inline]
+            #[must_use]
+            pub const fn base(&self) -> Base<'a> {
+                self.scope.base()
+            }
+
+            /// The language of the element.
+            ///
+            /// See the [`lang`][crate::lang] module for how the language is
+            /// determined.
+            #[inline]
+            #[must_use]
+            pub fn lang(&self) -> Option<Language<'a>> {
+                self.scope.lang()
+            }
         }
     };
     ($name:ident, $($nms:ident),+ $(,)?) => {
@@ -126,6 +158,7 @@ macro_rules! impl_iter {
             tag: Tag<'a>,
             reader: Reader<'a>,
             pos: usize,
+            scope: Scope<'a>,
         }
 
         impl_iter!($iter_name, $elem_ty, $fn_name);
@@ -136,6 +169,23 @@ macro_rules! impl_iter {
             pub const fn attributes(&self) -> Option<Attributes<'a>> {
                 self.tag.attributes()
             }
+
+            /// The `xml:base` values which apply to the element.
+            #[inline]
+            #[must_use]
+            pub const fn base(&self) -> Base<'a> {
+                self.scope.base()
+            }
+
+            /// The language of the element.
+            ///
+            /// See the [`lang`][crate::lang] module for how the language is
+            /// determined.
+            #[inline]
+            #[must_use]
+            pub fn lang(&self) -> Option<Language<'a>> {
+                self.scope.lang()
+            }
         }
     };
     ($iter_name:ident, $elem_ty:ident, $fn_name:expr) => {
@@ -154,12 +204,17 @@ macro_rules! impl_iter {
                                 &mut self.pos,
                             );
 
-                            return Some($fn_name(Tag::Start(tag), tag_name, content));
+                            return Some($fn_name(Tag::Start(tag), tag_name, content, self.scope));
                         }
                         token::Ty::EmptyElementTag(tag) => {
                             let tag_name = tag.name();
 
-                            return Some($fn_name(Tag::EmptyElement(tag), tag_name, ""));
+                            return Some($fn_name(
+                                Tag::EmptyElement(tag),
+                                tag_name,
+                                "",
+                                self.scope,
+                            ));
                         }
                         token::Ty::Characters(content) => {
                             if content.content().as_str().trim().is_empty() {
@@ -203,6 +258,33 @@ content_elem!(
     ImageDescription,
 );
 
+impl<'a> ImageUrl<'a> {
+    /// Resolves the content against the base URI of the element.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve(&self, document_url: &str) -> String {
+        self.scope
+            .base()
+            .resolve(document_url, &xml::decode(self.content))
+    }
+}
+
+impl<'a> ImageWidth<'a> {
+    /// Parses the content as a width in pixels.
+    #[inline]
+    pub fn value(&self) -> Result<u32, ParseError> {
+        value::parse_u32(self.content)
+    }
+}
+
+impl<'a> ImageHeight<'a> {
+    /// Parses the content as a height in pixels.
+    #[inline]
+    pub fn value(&self) -> Result<u32, ParseError> {
+        value::parse_u32(self.content)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ImageElem<'a> {
     Url(ImageUrl<'a>),
@@ -236,6 +318,73 @@ impl_attr!(ItemEnclosure, ty, "type");
 
 impl_attr!(ItemGuid, is_perma_link, "isPermaLink");
 
+impl<'a> ItemLink<'a> {
+    /// Resolves the content against the base URI of the element.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve(&self, document_url: &str) -> String {
+        self.scope
+            .base()
+            .resolve(document_url, &xml::decode(self.content))
+    }
+}
+
+impl<'a> ItemDescription<'a> {
+    /// The decoded HTML as plain text.
+    ///
+    /// See [`text::html_to_text()`][crate::text::html_to_text].
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        crate::text::html_to_text(&xml::decode(self.content))
+    }
+
+    /// The decoded HTML sanitized with the policy.
+    ///
+    /// Relative URLs are resolved against the base URI of the element.
+    #[cfg(feature = "html-sanitize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "html-sanitize")))]
+    #[must_use]
+    pub fn to_sanitized_html(&self, document_url: &str, policy: &Policy) -> String {
+        policy.sanitize(
+            &xml::decode(self.content),
+            Some(&self.scope.base().to_uri(document_url)),
+        )
+    }
+}
+
+impl<'a> ItemEnclosure<'a> {
+    /// Resolves the `url` attribute against the base URI of the element.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve_url(&self, document_url: &str) -> Option<String> {
+        let url = self.url()?;
+        Some(
+            self.scope
+                .base()
+                .resolve(document_url, &xml::decode(url.as_str())),
+        )
+    }
+
+    /// Parses the `length` attribute as a size in bytes.
+    #[inline]
+    #[must_use]
+    pub fn length_value(&self) -> Option<Result<u64, ParseError>> {
+        self.len().map(|len| value::parse_u64(len.as_str()))
+    }
+}
+
+impl<'a> ItemGuid<'a> {
+    /// Parses the `isPermaLink` attribute.
+    ///
+    /// If the attribute is missing, the GUID is a permalink.
+    #[inline]
+    pub fn is_perma_link_value(&self) -> Result<bool, ParseError> {
+        self.is_perma_link()
+            .map_or(Ok(true), |v| value::parse_bool(v.as_str()))
+    }
+}
+
 impl_attr!(ItemSource, url, "url");
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -250,12 +399,45 @@ pub enum ItemElem<'a> {
     Guid(ItemGuid<'a>),
     PubDate(ItemPubDate<'a>),
     Source(ItemSource<'a>),
+    AtomLink(atom::Link<'a>),
+    Content(content::Elem<'a>),
+    Dc(dc::Elem<'a>),
+    Itunes(itunes::Elem<'a>),
+    Media(media::Elem<'a>),
+    Podcast(podcast::Elem<'a>),
+    Unknown(Unknown<'a>),
+    Raw(Token<'a>),
+}
+
+content_elem!(
+    TextInputTitle,
+    TextInputDescription,
+    TextInputName,
+    TextInputLink,
+);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TextInputElem<'a> {
+    Title(TextInputTitle<'a>),
+    Description(TextInputDescription<'a>),
+    Name(TextInputName<'a>),
+    Link(TextInputLink<'a>),
     Unknown(Unknown<'a>),
     Raw(Token<'a>),
 }
 
 content_elem!(SkipHoursHour);
 
+impl<'a> SkipHoursHour<'a> {
+    /// Parses the content as an hour of the day in GMT from 0 to 23.
+    ///
+    /// An hour of 24 is treated as midnight (0).
+    #[inline]
+    pub fn value(&self) -> Result<u8, ParseError> {
+        value::parse_in_range(self.content, 24).map(|hour| (hour % 24) as u8)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SkipHoursElem<'a> {
     Hour(SkipHoursHour<'a>),
@@ -265,6 +447,14 @@ pub enum SkipHoursElem<'a> {
 
 content_elem!(SkipDaysDay);
 
+impl<'a> SkipDaysDay<'a> {
+    /// Parses the content as a day of the week.
+    #[inline]
+    pub fn value(&self) -> Result<Weekday, ParseError> {
+        self.content.parse()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SkipDaysElem<'a> {
     Day(SkipDaysDay<'a>),
@@ -284,11 +474,56 @@ content_elem!(
     ChannelLastBuildDate,
     ChannelGenerator,
     ChannelDocs,
+    ChannelCloud,
     ChannelTtl,
     ChannelRating,
     ChannelCategory,
 );
 
+macro_rules! impl_date {
+    ($name:ident $(,)?) => {
+        impl<'a> $name<'a> {
+            /// Parses the content as an RFC 822 date and time.
+            #[inline]
+            #[must_use]
+            pub fn to_date_time(&self) -> Option<DateTime> {
+                DateTime::parse_rfc2822(self.content)
+            }
+        }
+    };
+    ($name:ident, $($nms:ident),+ $(,)?) => {
+        impl_date!($name);
+        impl_date!($($nms),+);
+    };
+}
+
+impl_date!(ItemPubDate, ChannelPubDate, ChannelLastBuildDate);
+
+impl_attr!(ChannelCloud, domain, "domain");
+impl_attr!(ChannelCloud, port, "port");
+impl_attr!(ChannelCloud, path, "path");
+impl_attr!(ChannelCloud, register_procedure, "registerProcedure");
+impl_attr!(ChannelCloud, protocol, "protocol");
+
+impl<'a> ChannelLink<'a> {
+    /// Resolves the content against the base URI of the element.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve(&self, document_url: &str) -> String {
+        self.scope
+            .base()
+            .resolve(document_url, &xml::decode(self.content))
+    }
+}
+
+impl<'a> ChannelTtl<'a> {
+    /// Parses the content as a number of minutes.
+    #[inline]
+    pub fn value(&self) -> Result<u32, ParseError> {
+        value::parse_u32(self.content)
+    }
+}
+
 content_elem!(Category);
 impl_attr!(Category, domain, "domain");
 
@@ -306,13 +541,20 @@ pub enum ChannelElem<'a> {
     Category(ChannelCategory<'a>),
     Generator(ChannelGenerator<'a>),
     Docs(ChannelDocs<'a>),
-    // Cloud,
+    Cloud(ChannelCloud<'a>),
     Ttl(ChannelTtl<'a>),
     Image(ChannelImageIter<'a>),
     Rating(ChannelRating<'a>),
+    TextInput(ChannelTextInputIter<'a>),
     SkipHours(ChannelSkipHoursIter<'a>),
     SkipDays(ChannelSkipDaysIter<'a>),
     Item(ChannelItemIter<'a>),
+    AtomLink(atom::Link<'a>),
+    Dc(dc::Elem<'a>),
+    Fh(fh::Elem<'a>),
+    Itunes(itunes::Elem<'a>),
+    Podcast(podcast::Elem<'a>),
+    Sy(sy::Elem<'a>),
     Unknown(Unknown<'a>),
     Raw(Token<'a>),
 }
@@ -332,14 +574,29 @@ pub enum Elem<'a> {
     Raw(Token<'a>),
 }
 
+#[must_use]
+fn is_atom_link(tag_name: TagName<'_>) -> bool {
+    ext::has_prefix(tag_name, "atom") && tag_name.local().as_str().eq_ignore_ascii_case("link")
+}
+
 impl<'a> ImageElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> ImageElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> ImageElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
@@ -351,22 +608,68 @@ impl<'a> ImageElem<'a> {
         return_content!("height", ImageHeight, ImageElem::Height);
         return_content!("description", ImageDescription, ImageElem::Description);
 
-        ImageElem::Unknown(Unknown { tag, content })
+        ImageElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> ItemElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> ItemElem<'a> {
+    pub(crate) fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> ItemElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
 
+        if is_atom_link(tag_name) {
+            return ItemElem::AtomLink(atom::Link::new(tag, content, scope));
+        }
+
+        if content::is_content(tag_name) {
+            return ItemElem::Content(content::Elem::new(tag, tag_name, content, scope));
+        }
+
+        if dc::is_dc(tag_name) {
+            return ItemElem::Dc(dc::Elem::new(tag, tag_name, content));
+        }
+
+        if itunes::is_itunes(tag_name) {
+            return ItemElem::Itunes(itunes::Elem::new(tag, tag_name, content));
+        }
+
+        if media::is_media(tag_name) {
+            return ItemElem::Media(media::Elem::new(tag, tag_name, content));
+        }
+
+        if podcast::is_podcast(tag_name) {
+            return ItemElem::Podcast(podcast::Elem::new(tag, tag_name, content, scope));
+        }
+
+        if tag_name.namespace_prefix().is_some() {
+            return ItemElem::Unknown(Unknown {
+                tag,
+                content,
+                scope,
+            });
+        }
+
         return_content!("title", ItemTitle, ItemElem::Title);
         return_content!("link", ItemLink, ItemElem::Link);
         return_content!("description", ItemDescription, ItemElem::Description);
@@ -379,54 +682,135 @@ impl<'a> ItemElem<'a> {
         return_content!("enclosure", ItemEnclosure, ItemElem::Enclosure);
         return_content!("source", ItemSource, ItemElem::Source);
 
-        ItemElem::Unknown(Unknown { tag, content })
+        ItemElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
+    }
+}
+
+impl<'a> TextInputElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> TextInputElem<'a> {
+        let scope = scope.enter(tag);
+        let local_name = tag_name.local().as_str();
+
+        macro_rules! return_content {
+            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
+                if local_name.eq_ignore_ascii_case($local_name) {
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
+                }
+            };
+        }
+
+        return_content!("title", TextInputTitle, TextInputElem::Title);
+        return_content!(
+            "description",
+            TextInputDescription,
+            TextInputElem::Description
+        );
+        return_content!("name", TextInputName, TextInputElem::Name);
+        return_content!("link", TextInputLink, TextInputElem::Link);
+
+        TextInputElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> SkipHoursElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> SkipHoursElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> SkipHoursElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
 
         return_content!("hour", SkipHoursHour, SkipHoursElem::Hour);
 
-        SkipHoursElem::Unknown(Unknown { tag, content })
+        SkipHoursElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> SkipDaysElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> SkipDaysElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> SkipDaysElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
 
         return_content!("day", SkipDaysDay, SkipDaysElem::Day);
 
-        SkipDaysElem::Unknown(Unknown { tag, content })
+        SkipDaysElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> ChannelElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> ChannelElem<'a> {
+    fn new(
+        tag: Tag<'a>,
+        tag_name: TagName<'a>,
+        content: &'a str,
+        scope: Scope<'a>,
+    ) -> ChannelElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_content {
             ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
                 if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty { tag, content });
+                    return $elem_ty($inner_ty {
+                        tag,
+                        content,
+                        scope,
+                    });
                 }
             };
         }
@@ -438,11 +822,44 @@ impl<'a> ChannelElem<'a> {
                         tag,
                         reader: Reader::from_str(content),
                         pos: 0,
+                        scope,
                     });
                 }
             };
         }
 
+        if is_atom_link(tag_name) {
+            return ChannelElem::AtomLink(atom::Link::new(tag, content, scope));
+        }
+
+        if dc::is_dc(tag_name) {
+            return ChannelElem::Dc(dc::Elem::new(tag, tag_name, content));
+        }
+
+        if fh::is_fh(tag_name) {
+            return ChannelElem::Fh(fh::Elem::new(tag, tag_name, content));
+        }
+
+        if itunes::is_itunes(tag_name) {
+            return ChannelElem::Itunes(itunes::Elem::new(tag, tag_name, content));
+        }
+
+        if podcast::is_podcast(tag_name) {
+            return ChannelElem::Podcast(podcast::Elem::new(tag, tag_name, content, scope));
+        }
+
+        if sy::is_sy(tag_name) {
+            return ChannelElem::Sy(sy::Elem::new(tag, tag_name, content));
+        }
+
+        if tag_name.namespace_prefix().is_some() {
+            return ChannelElem::Unknown(Unknown {
+                tag,
+                content,
+                scope,
+            });
+        }
+
         return_iter!("item", ChannelItemIter, ChannelElem::Item);
 
         return_content!("title", ChannelTitle, ChannelElem::Title);
@@ -464,43 +881,50 @@ impl<'a> ChannelElem<'a> {
         );
         return_content!("generator", ChannelGenerator, ChannelElem::Generator);
         return_content!("docs", ChannelDocs, ChannelElem::Docs);
+        return_content!("cloud", ChannelCloud, ChannelElem::Cloud);
         return_content!("ttl", ChannelTtl, ChannelElem::Ttl);
         return_content!("rating", ChannelRating, ChannelElem::Rating);
 
         return_iter!("image", ChannelImageIter, ChannelElem::Image);
+        return_iter!("textInput", ChannelTextInputIter, ChannelElem::TextInput);
         return_iter!("skipHours", ChannelSkipHoursIter, ChannelElem::SkipHours);
         return_iter!("skipDays", ChannelSkipDaysIter, ChannelElem::SkipDays);
 
         return_content!("category", ChannelCategory, ChannelElem::Category);
 
-        ChannelElem::Unknown(Unknown { tag, content })
+        ChannelElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> RssElem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> RssElem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str, scope: Scope<'a>) -> RssElem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
-        macro_rules! return_iter {
-            ($local_name: literal, $inner_ty: ident, $elem_ty: expr) => {
-                if local_name.eq_ignore_ascii_case($local_name) {
-                    return $elem_ty($inner_ty {
-                        tag,
-                        reader: Reader::from_str(content),
-                        pos: 0,
-                    });
-                }
-            };
+        if local_name.eq_ignore_ascii_case("channel") {
+            return RssElem::Channel(ChannelIter {
+                tag,
+                reader: Reader::from_str(content),
+                pos: 0,
+                scope: scope.with_channel(content),
+            });
         }
 
-        return_iter!("channel", ChannelIter, RssElem::Channel);
-
-        RssElem::Unknown(Unknown { tag, content })
+        RssElem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl<'a> Elem<'a> {
-    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str) -> Elem<'a> {
+    fn new(tag: Tag<'a>, tag_name: TagName<'a>, content: &'a str, scope: Scope<'a>) -> Elem<'a> {
+        let scope = scope.enter(tag);
         let local_name = tag_name.local().as_str();
 
         macro_rules! return_iter {
@@ -510,6 +934,7 @@ impl<'a> Elem<'a> {
                         tag,
                         reader: Reader::from_str(content),
                         pos: 0,
+                        scope,
                     });
                 }
             };
@@ -517,12 +942,17 @@ impl<'a> Elem<'a> {
 
         return_iter!("rss", RssIter, Elem::Rss);
 
-        Elem::Unknown(Unknown { tag, content })
+        Elem::Unknown(Unknown {
+            tag,
+            content,
+            scope,
+        })
     }
 }
 
 impl_iter!(with_tag ChannelImageIter, ImageElem, ImageElem::new);
 impl_iter!(with_tag ChannelItemIter, ItemElem, ItemElem::new);
+impl_iter!(with_tag ChannelTextInputIter, TextInputElem, TextInputElem::new);
 impl_iter!(with_tag ChannelSkipHoursIter, SkipHoursElem, SkipHoursElem::new);
 impl_iter!(with_tag ChannelSkipDaysIter, SkipDaysElem, SkipDaysElem::new);
 impl_iter!(with_tag ChannelIter, ChannelElem, ChannelElem::new);
@@ -534,6 +964,7 @@ impl_attr!(RssIter, version, "version");
 pub struct Iter<'a> {
     reader: Reader<'a>,
     pos: usize,
+    scope: Scope<'a>,
 }
 
 impl<'a> Iter<'a> {
@@ -543,8 +974,20 @@ impl<'a> Iter<'a> {
         Self {
             reader: Reader::from_str(input),
             pos: 0,
+            scope: Scope::default(),
         }
     }
+
+    /// Sets the `Content-Language` of the document (e.g. from the HTTP
+    /// response).
+    ///
+    /// It is used as the language of elements which do not otherwise have a
+    /// language.
+    #[must_use]
+    pub fn with_content_language(mut self, language: &'a str) -> Self {
+        self.scope = self.scope.with_content_language(language);
+        self
+    }
 }
 
 impl_iter!(Iter, Elem, Elem::new);
@@ -693,4 +1136,308 @@ mod tests {
         assert_eq!(None, rss_iter.next());
         assert_eq!(None, iter.next());
     }
+
+    #[test]
+    fn eval_prefixed_elements_do_not_match_core_elements() {
+        let input = r#"
+<rss xmlns:media="http://search.yahoo.com/mrss/" xmlns:ex="https://example.com/ns">
+    <channel>
+        <item>
+            <media:title>Media Title</media:title>
+            <ex:description>Extension Description</ex:description>
+            <title>Item Title</title>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ItemElem::Media(media::Elem::Title(title))) = item_iter.next() {
+            assert_eq!("Media Title", title.content());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Unknown(unknown)) = item_iter.next() {
+            assert_eq!("ex:description", unknown.tag_name().as_str());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Title(title)) = item_iter.next() {
+            assert_eq!("Item Title", title.content());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, item_iter.next());
+    }
+
+    #[test]
+    fn eval_atom_link_in_channel_and_item() {
+        let input = r#"
+<rss xmlns:atom="http://www.w3.org/2005/Atom">
+    <channel>
+        <link>https://example.com/</link>
+        <atom:link href="https://example.com/feed.xml" rel="self" type="application/rss+xml" hreflang="en" title="Feed" length="1024"/>
+        <item>
+            <atom:link href="https://example.com/1/comments.xml" rel="replies"/>
+            <link>https://example.com/1</link>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ChannelElem::Link(link)) = channel_iter.next() {
+            assert_eq!("https://example.com/", link.content());
+        } else {
+            panic!();
+        }
+        if let Some(ChannelElem::AtomLink(link)) = channel_iter.next() {
+            assert_eq!(
+                Some("https://example.com/feed.xml"),
+                link.href().map(|v| v.as_str())
+            );
+            assert_eq!(Some("self"), link.rel().map(|v| v.as_str()));
+            assert_eq!(Some("application/rss+xml"), link.ty().map(|v| v.as_str()));
+            assert_eq!(Some("en"), link.hreflang().map(|v| v.as_str()));
+            assert_eq!(Some("Feed"), link.title().map(|v| v.as_str()));
+            assert_eq!(Some("1024"), link.length().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(ItemElem::AtomLink(link)) = item_iter.next() {
+            assert_eq!(Some("replies"), link.rel().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Link(link)) = item_iter.next() {
+            assert_eq!("https://example.com/1", link.content());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, item_iter.next());
+        assert_eq!(None, channel_iter.next());
+    }
+
+    #[test]
+    fn eval_cloud_and_text_input() {
+        let input = r#"
+<rss>
+    <channel>
+        <cloud domain="rpc.example.com" port="80" path="/RPC2" registerProcedure="pingMe" protocol="soap"/>
+        <textInput>
+            <title>Search</title>
+            <description>Search the archives</description>
+            <name>q</name>
+            <link>https://example.com/search</link>
+        </textInput>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ChannelElem::Cloud(cloud)) = channel_iter.next() {
+            assert_eq!(Some("rpc.example.com"), cloud.domain().map(|v| v.as_str()));
+            assert_eq!(Some("80"), cloud.port().map(|v| v.as_str()));
+            assert_eq!(Some("/RPC2"), cloud.path().map(|v| v.as_str()));
+            assert_eq!(
+                Some("pingMe"),
+                cloud.register_procedure().map(|v| v.as_str())
+            );
+            assert_eq!(Some("soap"), cloud.protocol().map(|v| v.as_str()));
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::TextInput(mut text_input_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(TextInputElem::Title(title)) = text_input_iter.next() {
+            assert_eq!("Search", title.content());
+        } else {
+            panic!();
+        }
+        if let Some(TextInputElem::Description(desc)) = text_input_iter.next() {
+            assert_eq!("Search the archives", desc.content());
+        } else {
+            panic!();
+        }
+        if let Some(TextInputElem::Name(name)) = text_input_iter.next() {
+            assert_eq!("q", name.content());
+        } else {
+            panic!();
+        }
+        if let Some(TextInputElem::Link(link)) = text_input_iter.next() {
+            assert_eq!("https://example.com/search", link.content());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, text_input_iter.next());
+        assert_eq!(None, channel_iter.next());
+    }
+
+    #[test]
+    fn eval_typed_values() {
+        let input = r#"
+<rss>
+    <channel>
+        <ttl> 60 </ttl>
+        <skipHours><hour>24</hour><hour>25</hour></skipHours>
+        <skipDays><day>saturday</day><day>Sat</day></skipDays>
+        <item>
+            <enclosure url="https://example.com/1.mp3" length="12216320" type="audio/mpeg"/>
+            <guid isPermaLink="False">1</guid>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = Iter::new(input);
+
+        let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+
+        if let Some(ChannelElem::Ttl(ttl)) = channel_iter.next() {
+            assert_eq!(Ok(60), ttl.value());
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::SkipHours(mut skip_hours_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(SkipHoursElem::Hour(hour)) = skip_hours_iter.next() {
+            assert_eq!(Ok(0), hour.value());
+        } else {
+            panic!();
+        }
+        if let Some(SkipHoursElem::Hour(hour)) = skip_hours_iter.next() {
+            assert_eq!(
+                Err(value::ErrorKind::OutOfRange),
+                hour.value().map_err(|e| e.kind())
+            );
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::SkipDays(mut skip_days_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(SkipDaysElem::Day(day)) = skip_days_iter.next() {
+            assert_eq!(Ok(Weekday::Saturday), day.value());
+        } else {
+            panic!();
+        }
+        if let Some(SkipDaysElem::Day(day)) = skip_days_iter.next() {
+            assert_eq!(
+                Err(value::ErrorKind::InvalidWeekday),
+                day.value().map_err(|e| e.kind())
+            );
+        } else {
+            panic!();
+        }
+
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(ItemElem::Enclosure(enclosure)) = item_iter.next() {
+            assert_eq!(Some(Ok(12_216_320)), enclosure.length_value());
+        } else {
+            panic!();
+        }
+        if let Some(ItemElem::Guid(guid)) = item_iter.next() {
+            assert_eq!(Ok(false), guid.is_perma_link_value());
+        } else {
+            panic!();
+        }
+        assert_eq!(None, item_iter.next());
+    }
+
+    #[test]
+    fn eval_channel_language() {
+        let input = r#"
+<rss xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <channel>
+        <dc:language>es</dc:language>
+        <item>
+            <title>Lorem</title>
+        </item>
+        <language> en_us </language>
+    </channel>
+    <channel>
+        <dc:language>es</dc:language>
+        <item>
+            <title xml:lang="fr">Ipsum</title>
+        </item>
+    </channel>
+</rss>
+        "#;
+
+        let mut iter = Iter::new(input).with_content_language("de");
+
+        let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        assert!(matches!(channel_iter.next(), Some(ChannelElem::Dc(_))));
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(ItemElem::Title(title)) = item_iter.next() {
+            assert_eq!(Some("en_us"), title.lang().map(|lang| lang.as_str()));
+        } else {
+            panic!();
+        }
+
+        let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        assert_eq!(Some("de"), channel_iter.lang().map(|lang| lang.as_str()));
+        assert!(matches!(channel_iter.next(), Some(ChannelElem::Dc(_))));
+        let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+            panic!();
+        };
+        if let Some(ItemElem::Title(title)) = item_iter.next() {
+            assert_eq!(Some("fr"), title.lang().map(|lang| lang.as_str()));
+        } else {
+            panic!();
+        }
+    }
 }
@@ -0,0 +1,628 @@
+//! Sanitizes untrusted HTML from feeds.
+//!
+//! Only elements and attributes from an allowlist suited to feed readers are
+//! kept. Scripts, styles, event handler attributes, and embedded documents are
+//! removed along with URLs which do not use the `http`, `https`, or `mailto`
+//! schemes. Images which are 1 pixel or less wide or high (usually tracking
+//! pixels) are removed. Relative URLs are resolved against the base URL.
+//!
+//! `iframe` elements are removed unless [`Policy::with_video_embeds()`] allows
+//! YouTube and Vimeo players.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::sanitize::Policy;
+//!
+//! let html = r#"<p onclick="steal()">Hello <a href="/about">world</a></p>
+//! <script>steal()</script><img src="/pixel.gif" width="1" height="1">"#;
+//!
+//! assert_eq!(
+//!     "<p>Hello <a href=\"https://example.com/about\">world</a></p>\n",
+//!     Policy::new().sanitize(html, Some("https://example.com/feed.xml"))
+//! );
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use maybe_xml::token::{self, prop::Attributes};
+
+use crate::{
+    text::{self, HtmlToken},
+    uri, value,
+};
+
+/// Attributes which are allowed on every element.
+const GLOBAL_ATTRIBUTES: &[&str] = &["dir", "lang", "title"];
+
+/// Allowed elements and their allowed attributes.
+const ELEMENTS: &[(&str, &[&str])] = &[
+    ("a", &["href"]),
+    ("abbr", &[]),
+    ("article", &[]),
+    ("aside", &[]),
+    ("audio", &["controls", "src"]),
+    ("b", &[]),
+    ("blockquote", &["cite"]),
+    ("br", &[]),
+    ("caption", &[]),
+    ("cite", &[]),
+    ("code", &[]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("dd", &[]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["open"]),
+    ("dfn", &[]),
+    ("div", &[]),
+    ("dl", &[]),
+    ("dt", &[]),
+    ("em", &[]),
+    ("figcaption", &[]),
+    ("figure", &[]),
+    ("footer", &[]),
+    ("h1", &[]),
+    ("h2", &[]),
+    ("h3", &[]),
+    ("h4", &[]),
+    ("h5", &[]),
+    ("h6", &[]),
+    ("header", &[]),
+    ("hr", &[]),
+    ("i", &[]),
+    ("img", &["alt", "height", "src", "width"]),
+    ("ins", &["cite", "datetime"]),
+    ("kbd", &[]),
+    ("li", &["value"]),
+    ("mark", &[]),
+    ("ol", &["reversed", "start", "type"]),
+    ("p", &[]),
+    ("pre", &[]),
+    ("q", &["cite"]),
+    ("s", &[]),
+    ("samp", &[]),
+    ("section", &[]),
+    ("small", &[]),
+    ("source", &["src", "type"]),
+    ("span", &[]),
+    ("strong", &[]),
+    ("sub", &[]),
+    ("summary", &[]),
+    ("sup", &[]),
+    ("table", &[]),
+    ("tbody", &[]),
+    ("td", &["colspan", "rowspan"]),
+    ("tfoot", &[]),
+    ("th", &["colspan", "rowspan", "scope"]),
+    ("thead", &[]),
+    ("time", &["datetime"]),
+    ("tr", &[]),
+    ("u", &[]),
+    ("ul", &[]),
+    ("video", &["controls", "height", "poster", "src", "width"]),
+];
+
+/// Elements which do not have an end tag.
+const VOID_ELEMENTS: &[&str] = &["br", "col", "hr", "img", "source"];
+
+/// Elements which are removed with their content.
+const REMOVED_ELEMENTS: &[&str] = &[
+    "applet", "frameset", "head", "iframe", "math", "noembed", "noframes", "noscript", "object",
+    "script", "select", "style", "svg", "template", "textarea", "title",
+];
+
+/// Attributes which are URLs.
+const URL_ATTRIBUTES: &[&str] = &["cite", "href", "poster", "src"];
+
+/// Schemes which are allowed in URLs.
+const URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Attributes which are allowed on embedded video players.
+const IFRAME_ATTRIBUTES: &[&str] = &["allowfullscreen", "height", "src", "width"];
+
+/// Hosts and path prefixes of the allowed embedded video players.
+const VIDEO_EMBEDS: &[(&str, &str)] = &[
+    ("www.youtube.com", "/embed/"),
+    ("youtube.com", "/embed/"),
+    ("www.youtube-nocookie.com", "/embed/"),
+    ("youtube-nocookie.com", "/embed/"),
+    ("player.vimeo.com", "/video/"),
+];
+
+#[must_use]
+fn is_one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// Decodes, resolves, and checks the scheme of a URL.
+///
+/// Returns `None` if the URL is not allowed.
+#[must_use]
+fn sanitize_url(value: &str, base_url: Option<&str>) -> Option<String> {
+    let mut decoded = String::with_capacity(value.len());
+    text::push_decoded(&mut decoded, value);
+    // Browsers ignore tabs and newlines anywhere in a URL.
+    decoded.retain(|ch| !matches!(ch, '\t' | '\n' | '\r'));
+    let url = decoded.trim_matches(|ch: char| ch <= ' ');
+
+    if let Some(scheme) = uri::scheme(url) {
+        return is_one_of(scheme, URL_SCHEMES).then(|| String::from(url));
+    }
+    // A colon before any path separator could be read as a scheme.
+    let first_segment = url.split(['/', '?', '#']).next().unwrap_or_default();
+    if first_segment.contains(':') {
+        return None;
+    }
+
+    let url = match base_url {
+        Some(base_url) => uri::resolve(base_url, url),
+        None => String::from(url),
+    };
+    uri::scheme(&url)
+        .map_or(true, |scheme| is_one_of(scheme, URL_SCHEMES))
+        .then_some(url)
+}
+
+/// Returns true if the URL is an allowed embedded video player.
+#[must_use]
+fn is_video_embed(url: &str) -> bool {
+    let Some(rest) = url
+        .get(..8)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("https://"))
+        .map(|_| &url[8..])
+    else {
+        return false;
+    };
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(end);
+    VIDEO_EMBEDS.iter().any(|(embed_host, prefix)| {
+        embed_host.eq_ignore_ascii_case(host) && path.starts_with(prefix)
+    })
+}
+
+/// Returns true if an image is 1 pixel or less wide or high.
+#[must_use]
+fn is_tracking_pixel(attributes: Option<Attributes<'_>>) -> bool {
+    ["width", "height"].into_iter().any(|name| {
+        crate::find_attribute(attributes, name)
+            .and_then(|size| {
+                let size = size.as_str().trim();
+                value::parse_u32(size.strip_suffix("px").unwrap_or(size)).ok()
+            })
+            .is_some_and(|size| size <= 1)
+    })
+}
+
+/// Appends an attribute. An empty value is written as an attribute without a
+/// value.
+fn push_attribute(output: &mut String, name: &str, value: Option<&str>) {
+    output.push(' ');
+    output.push_str(name);
+    if let Some(value) = value.filter(|value| !value.is_empty()) {
+        output.push_str("=\"");
+        output.push_str(&text::escape_html(value));
+        output.push('"');
+    }
+}
+
+/// Appends a start tag with the allowed attributes.
+fn push_start_tag(
+    output: &mut String,
+    name: &str,
+    allowed: &[&str],
+    attributes: Option<Attributes<'_>>,
+    base_url: Option<&str>,
+) {
+    output.push('<');
+    output.push_str(name);
+    let mut decoded = String::new();
+    for attr in attributes.into_iter().flatten() {
+        let Some(attr_name) = GLOBAL_ATTRIBUTES
+            .iter()
+            .chain(allowed)
+            .find(|n| n.eq_ignore_ascii_case(attr.name().as_str()))
+        else {
+            continue;
+        };
+        let value = attr.value().map(|value| value.as_str());
+        if is_one_of(attr_name, URL_ATTRIBUTES) {
+            if let Some(url) = value.and_then(|value| sanitize_url(value, base_url)) {
+                push_attribute(output, attr_name, Some(&url));
+            }
+        } else {
+            let value = value.map(|value| {
+                decoded.clear();
+                text::push_decoded(&mut decoded, value);
+                decoded.as_str()
+            });
+            push_attribute(output, attr_name, value);
+        }
+    }
+    output.push('>');
+}
+
+fn push_end_tag(output: &mut String, name: &str) {
+    output.push_str("</");
+    output.push_str(name);
+    output.push('>');
+}
+
+/// The rules for sanitizing HTML.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Policy {
+    video_embeds: bool,
+}
+
+impl Policy {
+    /// Creates the default policy which removes all embedded documents.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            video_embeds: false,
+        }
+    }
+
+    /// Sets if YouTube and Vimeo players in `iframe` elements are kept.
+    ///
+    /// Only `https` URLs of the players' embed pages are allowed.
+    #[inline]
+    #[must_use]
+    pub const fn with_video_embeds(mut self, video_embeds: bool) -> Self {
+        self.video_embeds = video_embeds;
+        self
+    }
+
+    /// Returns true if YouTube and Vimeo players in `iframe` elements are
+    /// kept.
+    ///
+    /// See [`Policy::with_video_embeds()`].
+    #[inline]
+    #[must_use]
+    pub const fn video_embeds(&self) -> bool {
+        self.video_embeds
+    }
+
+    /// Sanitizes HTML.
+    ///
+    /// Relative URLs are resolved against `base_url` if given. Elements which
+    /// are not allowed are removed but their content is kept unless the
+    /// content is never displayed (e.g. `script` elements). Unclosed elements
+    /// are closed at the end.
+    #[must_use]
+    pub fn sanitize(&self, html: &str, base_url: Option<&str>) -> String {
+        let mut sanitizer = Sanitizer {
+            policy: self,
+            base_url,
+            output: String::with_capacity(html.len()),
+            decoded: String::new(),
+            open: Vec::new(),
+            removed: None,
+        };
+
+        text::tokenize_html(html, |token| match token {
+            HtmlToken::Markup(ty) => sanitizer.push_token(ty),
+            HtmlToken::Text(text) => sanitizer.push_text(text),
+        });
+
+        sanitizer.finish()
+    }
+}
+
+/// The state of the HTML being sanitized.
+#[derive(Debug)]
+struct Sanitizer<'a> {
+    policy: &'a Policy,
+    base_url: Option<&'a str>,
+    output: String,
+    decoded: String,
+    /// The allowed elements which have not been closed
+    open: Vec<&'static str>,
+    /// The removed element and the depth of nested elements with the same name
+    removed: Option<(&'static str, usize)>,
+}
+
+impl<'a> Sanitizer<'a> {
+    fn push_token(&mut self, ty: token::Ty<'_>) {
+        match ty {
+            token::Ty::StartTag(tag) => {
+                let name = tag.name().local().as_str();
+                if let Some((removed_name, depth)) = &mut self.removed {
+                    if removed_name.eq_ignore_ascii_case(name) {
+                        *depth += 1;
+                    }
+                } else if let Some(removed_name) = find_removed_element(name) {
+                    self.push_iframe(removed_name, tag.attributes());
+                    self.removed = Some((removed_name, 1));
+                } else if let Some((elem, allowed)) = find_element(name, tag.attributes()) {
+                    push_start_tag(
+                        &mut self.output,
+                        elem,
+                        allowed,
+                        tag.attributes(),
+                        self.base_url,
+                    );
+                    if !is_one_of(elem, VOID_ELEMENTS) {
+                        self.open.push(elem);
+                    }
+                }
+            }
+            token::Ty::EmptyElementTag(tag) => {
+                if self.removed.is_some() {
+                    return;
+                }
+                let name = tag.name().local().as_str();
+                if let Some(removed_name) = find_removed_element(name) {
+                    self.push_iframe(removed_name, tag.attributes());
+                } else if let Some((elem, allowed)) = find_element(name, tag.attributes()) {
+                    push_start_tag(
+                        &mut self.output,
+                        elem,
+                        allowed,
+                        tag.attributes(),
+                        self.base_url,
+                    );
+                    if !is_one_of(elem, VOID_ELEMENTS) {
+                        push_end_tag(&mut self.output, elem);
+                    }
+                }
+            }
+            token::Ty::EndTag(tag) => {
+                let name = tag.name().local().as_str();
+                if let Some((removed_name, depth)) = &mut self.removed {
+                    if removed_name.eq_ignore_ascii_case(name) {
+                        *depth -= 1;
+                        if *depth == 0 {
+                            self.removed = None;
+                        }
+                    }
+                } else if let Some(index) = self
+                    .open
+                    .iter()
+                    .rposition(|elem| elem.eq_ignore_ascii_case(name))
+                {
+                    for elem in self.open.drain(index..).rev() {
+                        push_end_tag(&mut self.output, elem);
+                    }
+                }
+            }
+            token::Ty::Characters(chars) => self.push_text(chars.as_str()),
+            token::Ty::Cdata(cdata) => {
+                if self.removed.is_none() {
+                    self.output
+                        .push_str(&text::escape_html(cdata.content().as_str()));
+                }
+            }
+            token::Ty::ProcessingInstruction(_)
+            | token::Ty::Declaration(_)
+            | token::Ty::Comment(_) => {}
+        }
+    }
+
+    /// Appends text which may contain character references.
+    fn push_text(&mut self, text: &str) {
+        if self.removed.is_none() {
+            self.decoded.clear();
+            text::push_decoded(&mut self.decoded, text);
+            self.output.push_str(&text::escape_html(&self.decoded));
+        }
+    }
+
+    /// Appends an `iframe` element if it is an allowed video player.
+    fn push_iframe(&mut self, name: &str, attributes: Option<Attributes<'_>>) {
+        if !self.policy.video_embeds || name != "iframe" {
+            return;
+        }
+        let is_allowed = crate::find_attribute(attributes, "src")
+            .and_then(|src| sanitize_url(src.as_str(), self.base_url))
+            .is_some_and(|src| is_video_embed(&src));
+        if is_allowed {
+            push_start_tag(
+                &mut self.output,
+                "iframe",
+                IFRAME_ATTRIBUTES,
+                attributes,
+                self.base_url,
+            );
+            push_end_tag(&mut self.output, "iframe");
+        }
+    }
+
+    /// Closes the open elements and returns the sanitized HTML.
+    #[must_use]
+    fn finish(mut self) -> String {
+        for elem in self.open.drain(..).rev() {
+            push_end_tag(&mut self.output, elem);
+        }
+        self.output
+    }
+}
+
+#[must_use]
+fn find_removed_element(name: &str) -> Option<&'static str> {
+    REMOVED_ELEMENTS
+        .iter()
+        .find(|elem| elem.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Finds the allowed element and its allowed attributes.
+///
+/// Returns `None` if the element is not allowed or is a tracking pixel.
+#[must_use]
+fn find_element(
+    name: &str,
+    attributes: Option<Attributes<'_>>,
+) -> Option<(&'static str, &'static [&'static str])> {
+    let (elem, allowed) = ELEMENTS
+        .iter()
+        .find(|(elem, _)| elem.eq_ignore_ascii_case(name))?;
+    if *elem == "img" && is_tracking_pixel(attributes) {
+        return None;
+    }
+    Some((elem, allowed))
+}
+
+/// Sanitizes HTML with the default [`Policy`].
+#[must_use]
+pub fn sanitize(html: &str, base_url: Option<&str>) -> String {
+    Policy::new().sanitize(html, base_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_feed_content() {
+        assert_eq!(
+            "<p>Hello <b>world</b></p>",
+            sanitize(
+                "<p style=\"color: red\" onmouseover=\"alert(1)\">Hello <b>world</b></p>\
+                 <script>alert(1)</script><style>p { }</style><!-- comment -->",
+                None
+            )
+        );
+        assert_eq!(
+            "<a>one</a><a>two</a><a href=\"https://example.com/\">three</a>",
+            sanitize(
+                "<a href=\"javascript:alert(1)\">one</a>\
+                 <a href=\"java&#x09;script:alert(1)\">two</a>\
+                 <a HREF=\"https://example.com/\" target=\"_blank\">three</a>",
+                None
+            )
+        );
+        assert_eq!(
+            "<img src=\"https://example.com/blog/a.png\" alt=\"A &amp; B\"><br>",
+            sanitize(
+                "<img src=\"a.png\" alt=\"A &amp; B\"/><img src=\"/p.gif\" width=\"1\" \
+                 height=\"1\"><img src=\"/p.gif\" width=\"0px\"><br/>",
+                Some("https://example.com/blog/")
+            )
+        );
+        assert_eq!(
+            "<div><p>&lt;unclosed&gt;</p></div>",
+            sanitize("<div><font><p>&lt;unclosed&gt;</font></div></span>", None)
+        );
+        assert_eq!(
+            "<p>Text</p>",
+            sanitize(
+                "<p>Text<iframe src=\"https://evil.example/\">x</iframe></p>",
+                None
+            )
+        );
+
+        let html = "<iframe src=\"//www.youtube.com/embed/abc\" width=\"560\" allowfullscreen \
+                    onload=\"alert(1)\"><p>x</p></iframe>\
+                    <iframe src=\"https://evil.example/embed/abc\"></iframe>";
+        assert_eq!("", sanitize(html, Some("https://example.com/")));
+        assert_eq!(
+            "<video controls src=\"https://example.com/a.mp4\"></video> a &lt; b",
+            sanitize(
+                "<video controls src=a.mp4 onplay=alert(1)></video> a < b",
+                Some("https://example.com/")
+            )
+        );
+        assert_eq!(
+            "<iframe src=\"https://www.youtube.com/embed/abc\" width=\"560\" allowfullscreen>\
+             </iframe>",
+            Policy::new()
+                .with_video_embeds(true)
+                .sanitize(html, Some("https://example.com/"))
+        );
+    }
+
+    #[test]
+    fn sanitize_character_references_in_urls() {
+        for html in [
+            "<a href=\"&#x6A;avascript:alert(1)\">a</a>",
+            "<a href=\"jav&#x0A;ascript:alert(1)\">a</a>",
+            "<a href=\"&#106;&#97;vascript:alert(1)\">a</a>",
+        ] {
+            assert_eq!("<a>a</a>", sanitize(html, None), "{html}");
+        }
+        for html in [
+            "<a href=\"&#106avascript:alert(1)\">a</a>",
+            "<a href=\"&#0000106&#0000097vascript:alert(1)\">a</a>",
+        ] {
+            assert_eq!("a", sanitize(html, None), "{html}");
+        }
+    }
+
+    #[test]
+    fn sanitize_url_schemes() {
+        for html in [
+            "<a href=\"data:text/html;base64,PHNjcmlwdD4=\">a</a>",
+            "<a href=\"vbscript:msgbox(1)\">a</a>",
+            "<a href=\" VBScript:msgbox(1)\">a</a>",
+        ] {
+            assert_eq!("<a>a</a>", sanitize(html, None), "{html}");
+        }
+        assert_eq!(
+            "<img>",
+            sanitize("<img src=\"DATA:image/svg+xml,x\">", None)
+        );
+    }
+
+    #[test]
+    fn sanitize_upper_case_tags() {
+        assert_eq!(
+            "<p>A<b>b</b></p><img src=\"https://example.com/a.png\">",
+            sanitize(
+                "<P ONCLICK=\"x()\">A<B>b</B></P><SCRIPT>x()</SCRIPT>\
+                 <ScRiPt>x()</sCrIpT><IMG SRC=\"a.png\">",
+                Some("https://example.com/")
+            )
+        );
+    }
+
+    #[test]
+    fn sanitize_svg_and_unclosed_script() {
+        assert_eq!(
+            "<p>after</p>",
+            sanitize("<svg><script>alert(1)</script></svg><p>after</p>", None)
+        );
+        assert_eq!("", sanitize("<svg><p>inside</p>", None));
+        assert_eq!(
+            "<p>before</p>",
+            sanitize("<p>before</p><script>alert(1)", None)
+        );
+        assert_eq!(
+            "<p>before</p>",
+            sanitize("<p>before</p><script>alert(1)</p><b>x</b>", None)
+        );
+    }
+
+    #[test]
+    fn sanitize_video_embed_hosts() {
+        let policy = Policy::new().with_video_embeds(true);
+        for src in [
+            "https://www.youtube.com.evil.com/embed/abc",
+            "https://www.youtube.com@evil.com/embed/abc",
+            "https://user@www.youtube.com/embed/abc",
+            "https://evil.com\\@www.youtube.com/embed/abc",
+            "https://www.youtube.com\\.evil.com/embed/abc",
+            "https://evil.com\\www.youtube.com/embed/abc",
+            "http://www.youtube.com/embed/abc",
+            "https://www.youtube.com/watch?v=abc",
+        ] {
+            let html = alloc::format!("<iframe src=\"{src}\"></iframe>");
+            assert_eq!("", policy.sanitize(&html, None), "{src}");
+        }
+
+        let html = "<iframe src=\"https://player.vimeo.com/video/1\"></iframe>";
+        assert_eq!(html, policy.sanitize(html, None));
+        assert!(policy.video_embeds());
+
+        let policy = Policy::new().with_video_embeds(false);
+        assert!(!policy.video_embeds());
+        assert_eq!("", policy.sanitize(html, None));
+        assert_eq!(
+            "",
+            policy.sanitize(
+                "<iframe src=\"https://www.youtube.com/embed/abc\"></iframe>",
+                None
+            )
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Computes when a feed should next be polled from the update hints in an RSS
+//! channel.
+//!
+//! The hints are the `ttl`, `skipHours`, and `skipDays` channel elements and
+//! the [RSS syndication module][sy] elements. The result is always clamped to
+//! the bounds of the caller's [`Policy`].
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use core::time::Duration;
+//!
+//! use readfeed::rss::{self, Elem, RssElem};
+//! use readfeed::schedule::{Hints, Policy};
+//!
+//! let input = "
+//! <rss>
+//!     <channel>
+//!         <ttl>120</ttl>
+//!     </channel>
+//! </rss>
+//! ";
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! let hints = Hints::from_channel(channel_iter);
+//! let policy = Policy::new(Duration::from_secs(15 * 60), Duration::from_secs(86_400));
+//!
+//! // 2024-01-01T00:00:00Z
+//! let now = 1_704_067_200;
+//! assert_eq!(now + 2 * 3600, hints.next_poll(now, &policy));
+//! ```
+//!
+//! [sy]: https://web.resource.org/rss/1.0/modules/syndication/
+
+use core::time::Duration;
+
+use crate::{
+    date::DateTime,
+    ext::sy::{self, Period},
+    rss::{ChannelElem, ChannelIter, SkipDaysElem, SkipHoursElem},
+    value::Weekday,
+};
+
+const ALL_HOURS: u32 = (1 << 24) - 1;
+const ALL_DAYS: u8 = (1 << 7) - 1;
+
+/// The caller's bounds on the time between polls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Policy {
+    min_interval: Duration,
+    max_interval: Duration,
+    default_interval: Duration,
+}
+
+impl Policy {
+    /// Creates a policy which polls no more often than `min_interval` and no
+    /// less often than `max_interval`.
+    ///
+    /// If `max_interval` is less than `min_interval`, `min_interval` is used
+    /// for both. The default interval for channels without hints is one hour.
+    #[must_use]
+    pub fn new(min_interval: Duration, max_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            max_interval: max_interval.max(min_interval),
+            default_interval: Duration::from_secs(3600),
+        }
+    }
+
+    /// Sets the interval used when a channel has no update hints.
+    #[must_use]
+    pub const fn with_default_interval(mut self, default_interval: Duration) -> Self {
+        self.default_interval = default_interval;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn default_interval(&self) -> Duration {
+        self.default_interval
+    }
+}
+
+/// The update hints of a channel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hints {
+    ttl: Option<u32>,
+    skip_hours: u32,
+    skip_days: u8,
+    update_period: Option<Period>,
+    update_frequency: Option<u32>,
+    update_base: Option<DateTime>,
+}
+
+#[must_use]
+fn secs(duration: Duration) -> i64 {
+    i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+}
+
+impl Hints {
+    /// Reads the update hints from a channel.
+    ///
+    /// Values which cannot be parsed are ignored.
+    #[must_use]
+    pub fn from_channel(channel: ChannelIter<'_>) -> Self {
+        let mut hints = Self::default();
+        for elem in channel {
+            match elem {
+                ChannelElem::Ttl(ttl) => {
+                    if let Ok(ttl) = ttl.value() {
+                        hints.ttl = Some(ttl);
+                    }
+                }
+                ChannelElem::SkipHours(skip_hours) => {
+                    for elem in skip_hours {
+                        if let SkipHoursElem::Hour(hour) = elem {
+                            if let Ok(hour) = hour.value() {
+                                hints.skip_hours |= 1 << hour;
+                            }
+                        }
+                    }
+                }
+                ChannelElem::SkipDays(skip_days) => {
+                    for elem in skip_days {
+                        if let SkipDaysElem::Day(day) = elem {
+                            if let Ok(day) = day.value() {
+                                hints.skip_days |= 1 << day.days_from_monday();
+                            }
+                        }
+                    }
+                }
+                ChannelElem::Sy(sy::Elem::UpdatePeriod(period)) => {
+                    hints.update_period = period.value().or(hints.update_period);
+                }
+                ChannelElem::Sy(sy::Elem::UpdateFrequency(frequency)) => {
+                    if let Ok(frequency) = frequency.value() {
+                        hints.update_frequency = Some(frequency);
+                    }
+                }
+                ChannelElem::Sy(sy::Elem::UpdateBase(base)) => {
+                    hints.update_base = base.to_date_time().or(hints.update_base);
+                }
+                _ => {}
+            }
+        }
+        hints
+    }
+
+    /// The time to live from the `ttl` element.
+    #[must_use]
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl.map(|ttl| Duration::from_secs(u64::from(ttl) * 60))
+    }
+
+    /// Returns true if the hour (in GMT) is listed in `skipHours`.
+    #[must_use]
+    pub const fn is_hour_skipped(&self, hour: u8) -> bool {
+        hour < 24 && self.skip_hours & (1 << hour) != 0
+    }
+
+    /// Returns true if the day is listed in `skipDays`.
+    #[must_use]
+    pub const fn is_day_skipped(&self, day: Weekday) -> bool {
+        self.skip_days & (1 << day.days_from_monday()) != 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn update_period(&self) -> Option<Period> {
+        self.update_period
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn update_frequency(&self) -> Option<u32> {
+        self.update_frequency
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn update_base(&self) -> Option<DateTime> {
+        self.update_base
+    }
+
+    /// The time between updates from the syndication module.
+    ///
+    /// The period defaults to daily and the frequency defaults to 1 if only
+    /// one of them is given.
+    #[must_use]
+    pub fn update_interval(&self) -> Option<Duration> {
+        if self.update_period.is_none() && self.update_frequency.is_none() {
+            return None;
+        }
+        let period = self.update_period.unwrap_or(Period::Daily);
+        let frequency = self.update_frequency.filter(|f| *f > 0).unwrap_or(1);
+        Some(Duration::from_secs(period.seconds() / u64::from(frequency)))
+    }
+
+    /// The suggested time between polls.
+    ///
+    /// If both a `ttl` and a syndication module interval are given, the longer
+    /// one is used.
+    #[must_use]
+    pub fn interval(&self) -> Option<Duration> {
+        match (self.ttl(), self.update_interval()) {
+            (Some(ttl), Some(update)) => Some(ttl.max(update)),
+            (ttl, update) => ttl.or(update),
+        }
+    }
+
+    /// The time the feed should next be polled as the number of seconds since
+    /// the Unix epoch.
+    ///
+    /// Starting from the [interval][Hints::interval] (or the policy's default
+    /// interval) after `now`, the time is moved to the next scheduled update
+    /// if `sy:updateBase` is given and then past any skipped hours and days.
+    /// Finally, the time is clamped to the policy's bounds. Skipped hours and
+    /// days are ignored if every hour or every day is skipped.
+    #[must_use]
+    pub fn next_poll(&self, now: i64, policy: &Policy) -> i64 {
+        let interval = self
+            .interval()
+            .unwrap_or(policy.default_interval)
+            .clamp(policy.min_interval, policy.max_interval);
+        let mut next = now.saturating_add(secs(interval));
+
+        if let (Some(base), Some(update_interval)) = (self.update_base, self.update_interval()) {
+            let base = base.unix_timestamp();
+            let step = secs(update_interval);
+            if next <= base {
+                next = base;
+            } else if step > 0 {
+                let periods = (next - base + step - 1) / step;
+                next = base.saturating_add(periods.saturating_mul(step));
+            }
+        }
+
+        if self.skip_hours != ALL_HOURS && self.skip_days != ALL_DAYS {
+            loop {
+                let days = next.div_euclid(86_400);
+                let day = (days + 3).rem_euclid(7);
+                let hour = next.rem_euclid(86_400) / 3600;
+                if self.skip_days & (1 << day) != 0 {
+                    next = (days + 1) * 86_400;
+                } else if self.skip_hours & (1 << hour) != 0 {
+                    next = days * 86_400 + (hour + 1) * 3600;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        next.clamp(
+            now.saturating_add(secs(policy.min_interval)),
+            now.saturating_add(secs(policy.max_interval)),
+        )
+    }
+}
+
+/// Computes the time a channel should next be polled as the number of seconds
+/// since the Unix epoch.
+///
+/// See [`Hints::next_poll()`].
+#[must_use]
+pub fn next_poll(channel: ChannelIter<'_>, now: i64, policy: &Policy) -> i64 {
+    Hints::from_channel(channel).next_poll(now, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rss::{Elem, Iter, RssElem};
+
+    fn hints(input: &str) -> Hints {
+        let mut iter = Iter::new(input);
+        let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+            panic!();
+        };
+        let Some(RssElem::Channel(channel_iter)) = rss_iter.next() else {
+            panic!();
+        };
+        Hints::from_channel(channel_iter)
+    }
+
+    #[test]
+    fn next_poll_with_hints() {
+        let policy = Policy::new(
+            Duration::from_secs(15 * 60),
+            Duration::from_secs(7 * 86_400),
+        );
+        // 2024-01-05T10:30:00Z (a Friday)
+        let now = 1_704_450_600;
+
+        let no_hints = hints("<rss><channel></channel></rss>");
+        assert_eq!(None, no_hints.interval());
+        assert_eq!(now + 3600, no_hints.next_poll(now, &policy));
+
+        let ttl = hints("<rss><channel><ttl>5</ttl></channel></rss>");
+        assert_eq!(now + 15 * 60, ttl.next_poll(now, &policy));
+
+        let sy = hints(
+            r#"
+<rss xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+    <channel>
+        <ttl>60</ttl>
+        <sy:updatePeriod>daily</sy:updatePeriod>
+        <sy:updateFrequency>4</sy:updateFrequency>
+        <sy:updateBase>2000-01-01T00:00+00:00</sy:updateBase>
+    </channel>
+</rss>
+            "#,
+        );
+        assert_eq!(Some(Duration::from_secs(6 * 3600)), sy.interval());
+        // 2024-01-05T18:00:00Z
+        assert_eq!(1_704_477_600, sy.next_poll(now, &policy));
+
+        let skip = hints(
+            "
+<rss>
+    <channel>
+        <ttl>60</ttl>
+        <skipHours><hour>11</hour><hour>12</hour></skipHours>
+        <skipDays><day>Saturday</day><day>Sunday</day></skipDays>
+    </channel>
+</rss>
+            ",
+        );
+        assert!(skip.is_hour_skipped(11));
+        assert!(skip.is_day_skipped(Weekday::Sunday));
+        // 2024-01-05T13:00:00Z
+        assert_eq!(1_704_459_600, skip.next_poll(now, &policy));
+        // From 2024-01-05T23:30:00Z to 2024-01-08T00:00:00Z (a Monday)
+        assert_eq!(1_704_672_000, skip.next_poll(now + 13 * 3600, &policy));
+
+        let policy = Policy::new(Duration::from_secs(60), Duration::from_secs(3600));
+        assert_eq!(
+            now + 3600,
+            skip.next_poll(now + 13 * 3600, &policy) - 13 * 3600
+        );
+    }
+}
@@ -0,0 +1,739 @@
+//! Converts between plain text and HTML.
+//!
+//! The functions are useful for notifications, search snippets, and other
+//! places where markup cannot be displayed.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::rss::{self, ChannelElem, Elem, ItemElem, RssElem};
+//! use readfeed::text;
+//!
+//! let input = "
+//! <rss>
+//!     <channel>
+//!         <item>
+//!             <description>&lt;p&gt;Lorem ipsum dolor sit amet.&lt;/p&gt;
+//!                 &lt;p&gt;Consectetur adipiscing elit.&lt;/p&gt;</description>
+//!         </item>
+//!     </channel>
+//! </rss>
+//! ";
+//!
+//! let mut iter = rss::Iter::new(input);
+//!
+//! let Some(Elem::Rss(mut rss_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(RssElem::Channel(mut channel_iter)) = rss_iter.next() else {
+//!     panic!();
+//! };
+//! let Some(ChannelElem::Item(mut item_iter)) = channel_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(ItemElem::Description(description)) = item_iter.next() {
+//!     let plain_text = description.to_plain_text();
+//!     assert_eq!(
+//!         "Lorem ipsum dolor sit amet.\n\nConsectetur adipiscing elit.",
+//!         plain_text
+//!     );
+//!     assert_eq!("Lorem ipsum dolor sit…", text::truncate(&plain_text, 24));
+//! } else {
+//!     panic!();
+//! }
+//! ```
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::Write;
+
+use maybe_xml::{
+    token::{self, prop::Attributes},
+    Reader,
+};
+
+use crate::value;
+
+/// Common named character references in HTML, including the five which are
+/// predefined in XML.
+const HTML_ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '\u{a9}'),
+    ("reg", '\u{ae}'),
+    ("trade", '\u{2122}'),
+    ("hellip", '\u{2026}'),
+    ("mdash", '\u{2014}'),
+    ("ndash", '\u{2013}'),
+    ("lsquo", '\u{2018}'),
+    ("rsquo", '\u{2019}'),
+    ("sbquo", '\u{201a}'),
+    ("ldquo", '\u{201c}'),
+    ("rdquo", '\u{201d}'),
+    ("bdquo", '\u{201e}'),
+    ("laquo", '\u{ab}'),
+    ("raquo", '\u{bb}'),
+    ("bull", '\u{2022}'),
+    ("middot", '\u{b7}'),
+    ("deg", '\u{b0}'),
+    ("times", '\u{d7}'),
+    ("divide", '\u{f7}'),
+    ("euro", '\u{20ac}'),
+    ("pound", '\u{a3}'),
+    ("yen", '\u{a5}'),
+    ("cent", '\u{a2}'),
+    ("sect", '\u{a7}'),
+    ("para", '\u{b6}'),
+    ("iexcl", '\u{a1}'),
+    ("iquest", '\u{bf}'),
+    ("szlig", '\u{df}'),
+    ("agrave", '\u{e0}'),
+    ("aacute", '\u{e1}'),
+    ("acirc", '\u{e2}'),
+    ("atilde", '\u{e3}'),
+    ("auml", '\u{e4}'),
+    ("aring", '\u{e5}'),
+    ("aelig", '\u{e6}'),
+    ("ccedil", '\u{e7}'),
+    ("egrave", '\u{e8}'),
+    ("eacute", '\u{e9}'),
+    ("ecirc", '\u{ea}'),
+    ("euml", '\u{eb}'),
+    ("igrave", '\u{ec}'),
+    ("iacute", '\u{ed}'),
+    ("icirc", '\u{ee}'),
+    ("iuml", '\u{ef}'),
+    ("ntilde", '\u{f1}'),
+    ("ograve", '\u{f2}'),
+    ("oacute", '\u{f3}'),
+    ("ocirc", '\u{f4}'),
+    ("otilde", '\u{f5}'),
+    ("ouml", '\u{f6}'),
+    ("oslash", '\u{f8}'),
+    ("ugrave", '\u{f9}'),
+    ("uacute", '\u{fa}'),
+    ("ucirc", '\u{fb}'),
+    ("uuml", '\u{fc}'),
+    ("yacute", '\u{fd}'),
+    ("yuml", '\u{ff}'),
+    ("Agrave", '\u{c0}'),
+    ("Aacute", '\u{c1}'),
+    ("Acirc", '\u{c2}'),
+    ("Atilde", '\u{c3}'),
+    ("Auml", '\u{c4}'),
+    ("Aring", '\u{c5}'),
+    ("AElig", '\u{c6}'),
+    ("Ccedil", '\u{c7}'),
+    ("Egrave", '\u{c8}'),
+    ("Eacute", '\u{c9}'),
+    ("Ecirc", '\u{ca}'),
+    ("Euml", '\u{cb}'),
+    ("Igrave", '\u{cc}'),
+    ("Iacute", '\u{cd}'),
+    ("Icirc", '\u{ce}'),
+    ("Iuml", '\u{cf}'),
+    ("Ntilde", '\u{d1}'),
+    ("Ograve", '\u{d2}'),
+    ("Oacute", '\u{d3}'),
+    ("Ocirc", '\u{d4}'),
+    ("Otilde", '\u{d5}'),
+    ("Ouml", '\u{d6}'),
+    ("Oslash", '\u{d8}'),
+    ("Ugrave", '\u{d9}'),
+    ("Uacute", '\u{da}'),
+    ("Ucirc", '\u{db}'),
+    ("Uuml", '\u{dc}'),
+    ("Yacute", '\u{dd}'),
+];
+
+/// Elements which are separated from other content by a blank line.
+const PARAGRAPH_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dl",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "p",
+    "pre",
+    "section",
+    "table",
+];
+
+/// Elements which start on a new line.
+const LINE_ELEMENTS: &[&str] = &[
+    "caption",
+    "dd",
+    "div",
+    "dt",
+    "figcaption",
+    "li",
+    "summary",
+    "tr",
+];
+
+/// Elements which are separated from other content by a space.
+const CELL_ELEMENTS: &[&str] = &["td", "th"];
+
+/// Elements whose content is not text.
+const HIDDEN_ELEMENTS: &[&str] = &["head", "script", "style", "template", "noscript"];
+
+#[must_use]
+fn is_one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// Appends the text with HTML character references replaced.
+///
+/// Unknown references are left as is.
+pub(crate) fn push_decoded(output: &mut String, input: &str) {
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 32).and_then(|end| {
+            let name = &rest[1..end];
+            let ch = if let Some(num) = name.strip_prefix('#') {
+                let code = match num.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => num.parse().ok(),
+                };
+                code.and_then(char::from_u32)
+            } else {
+                HTML_ENTITIES
+                    .iter()
+                    .find_map(|(entity, ch)| (*entity == name).then_some(*ch))
+            };
+            ch.map(|ch| (ch, end))
+        });
+
+        if let Some((ch, end)) = decoded {
+            output.push(ch);
+            rest = &rest[end + 1..];
+        } else {
+            output.push('&');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+}
+
+/// Rewrites an HTML tag at the start of the input so it can be read as XML.
+///
+/// Attributes without a value are given an empty value and unquoted values
+/// are quoted. Returns the tag and the length of the input which was read, or
+/// `None` if the input does not start with a tag.
+#[must_use]
+fn normalize_tag(input: &str) -> Option<(String, usize)> {
+    let bytes = input.as_bytes();
+    let is_delimiter = |b: u8| b.is_ascii_whitespace() || matches!(b, b'>' | b'/' | b'=');
+    let skip_whitespace = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        i
+    };
+    let skip_name = |mut i: usize| {
+        while bytes.get(i).is_some_and(|b| !is_delimiter(*b)) {
+            i += 1;
+        }
+        i
+    };
+
+    let start = if input.starts_with("</") { 2 } else { 1 };
+    if bytes.first() != Some(&b'<') || !bytes.get(start).is_some_and(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    let mut i = skip_name(start);
+    let mut output = String::with_capacity(input.len().min(256));
+    output.push_str(&input[..i]);
+
+    loop {
+        i = skip_whitespace(i);
+        match bytes.get(i)? {
+            b'>' => {
+                output.push('>');
+                return Some((output, i + 1));
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                output.push_str("/>");
+                return Some((output, i + 2));
+            }
+            b'/' | b'=' => i += 1,
+            _ => {
+                let name_start = i;
+                i = skip_name(i);
+                output.push(' ');
+                output.push_str(&input[name_start..i]);
+
+                let value_start = skip_whitespace(i);
+                if bytes.get(value_start) != Some(&b'=') {
+                    output.push_str("=\"\"");
+                    continue;
+                }
+                i = skip_whitespace(value_start + 1);
+                let value = match bytes.get(i)? {
+                    quote @ (b'"' | b'\'') => {
+                        let end = i + 1 + input[i + 1..].find(char::from(*quote))?;
+                        let value = &input[i + 1..end];
+                        i = end + 1;
+                        value
+                    }
+                    _ => {
+                        let value_start = i;
+                        while bytes
+                            .get(i)
+                            .is_some_and(|b| !b.is_ascii_whitespace() && *b != b'>')
+                        {
+                            i += 1;
+                        }
+                        &input[value_start..i]
+                    }
+                };
+                output.push_str("=\"");
+                output.push_str(&value.replace('"', "&quot;"));
+                output.push('"');
+            }
+        }
+    }
+}
+
+/// A token of HTML markup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HtmlToken<'a> {
+    /// A token which could be read as XML.
+    Markup(token::Ty<'a>),
+    /// Text which could not be read as XML (e.g. a `<` which does not start a
+    /// tag).
+    Text(&'a str),
+}
+
+/// Calls the function for each token of HTML markup.
+///
+/// Tags which are not well-formed XML (e.g. `<video controls>`) are rewritten
+/// before they are read.
+pub(crate) fn tokenize_html(html: &str, mut f: impl FnMut(HtmlToken<'_>)) {
+    let reader = Reader::from_str(html);
+    let mut pos = 0;
+    loop {
+        while let Some(token) = reader.tokenize(&mut pos) {
+            f(HtmlToken::Markup(token.ty()));
+        }
+        let Some(rest) = html.get(pos..).filter(|rest| !rest.is_empty()) else {
+            return;
+        };
+        if let Some((tag, len)) = normalize_tag(rest) {
+            let mut tag_pos = 0;
+            if let Some(token) = Reader::from_str(&tag).tokenize(&mut tag_pos) {
+                f(HtmlToken::Markup(token.ty()));
+            }
+            pos += len;
+        } else {
+            let len = rest[1..].find('<').map_or(rest.len(), |i| i + 1);
+            f(HtmlToken::Text(&rest[..len]));
+            pos += len;
+        }
+    }
+}
+
+/// Builds plain text from HTML elements and text.
+#[derive(Debug, Default)]
+struct PlainText {
+    output: String,
+    /// The number of line breaks before the next text
+    pending_breaks: usize,
+    pending_space: bool,
+    /// The list item marker before the next text
+    pending_marker: String,
+    /// The next number of each open list or `None` for unordered lists
+    lists: Vec<Option<u32>>,
+    /// The number of open `pre` elements
+    pre_depth: usize,
+}
+
+impl PlainText {
+    fn push_break(&mut self, breaks: usize) {
+        self.pending_breaks = self.pending_breaks.max(breaks);
+        self.pending_space = false;
+    }
+
+    fn flush(&mut self) {
+        if !self.output.is_empty() {
+            if self.pending_breaks > 0 {
+                for _ in 0..self.pending_breaks {
+                    self.output.push('\n');
+                }
+            } else if self.pending_space {
+                self.output.push(' ');
+            }
+        }
+        self.output.push_str(&self.pending_marker);
+        self.pending_marker.clear();
+        self.pending_breaks = 0;
+        self.pending_space = false;
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            if self.pre_depth > 0 && ch == '\n' && self.pending_breaks > 0 {
+                // The line break after a `pre` start tag is ignored
+            } else if self.pre_depth == 0 && ch.is_whitespace() {
+                self.pending_space = self.pending_breaks == 0;
+            } else {
+                self.flush();
+                self.output.push(ch);
+            }
+        }
+    }
+
+    fn start_element(&mut self, name: &str, attributes: Option<Attributes<'_>>) {
+        if name.eq_ignore_ascii_case("br") {
+            // Consecutive line breaks separate paragraphs
+            self.push_break((self.pending_breaks + 1).min(2));
+        } else if name.eq_ignore_ascii_case("ul") || name.eq_ignore_ascii_case("ol") {
+            self.push_break(if self.lists.is_empty() { 2 } else { 1 });
+            self.lists.push(name.eq_ignore_ascii_case("ol").then(|| {
+                crate::find_attribute(attributes, "start")
+                    .and_then(|start| value::parse_u32(start.as_str()).ok())
+                    .unwrap_or(1)
+            }));
+        } else if name.eq_ignore_ascii_case("li") {
+            self.push_break(1);
+            self.pending_marker.clear();
+            for _ in 1..self.lists.len() {
+                self.pending_marker.push_str("  ");
+            }
+            if let Some(Some(number)) = self.lists.last_mut() {
+                let _ = write!(self.pending_marker, "{number}. ");
+                *number = number.saturating_add(1);
+            } else {
+                self.pending_marker.push_str("\u{2022} ");
+            }
+        } else if is_one_of(name, PARAGRAPH_ELEMENTS) {
+            self.push_break(2);
+            if name.eq_ignore_ascii_case("pre") {
+                self.pre_depth += 1;
+            }
+        } else if is_one_of(name, LINE_ELEMENTS) {
+            self.push_break(1);
+        } else if is_one_of(name, CELL_ELEMENTS) {
+            self.pending_space = self.pending_breaks == 0;
+        }
+    }
+
+    fn end_element(&mut self, name: &str) {
+        if name.eq_ignore_ascii_case("ul") || name.eq_ignore_ascii_case("ol") {
+            self.lists.pop();
+            self.push_break(if self.lists.is_empty() { 2 } else { 1 });
+        } else if is_one_of(name, PARAGRAPH_ELEMENTS) {
+            self.push_break(2);
+            if name.eq_ignore_ascii_case("pre") {
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+            }
+        } else if is_one_of(name, LINE_ELEMENTS) {
+            self.push_break(1);
+        }
+    }
+
+    fn empty_element(&mut self, name: &str) {
+        if name.eq_ignore_ascii_case("br") {
+            self.start_element(name, None);
+        } else if name.eq_ignore_ascii_case("ul")
+            || name.eq_ignore_ascii_case("ol")
+            || is_one_of(name, PARAGRAPH_ELEMENTS)
+        {
+            self.push_break(2);
+        } else if is_one_of(name, LINE_ELEMENTS) {
+            self.push_break(1);
+        } else if is_one_of(name, CELL_ELEMENTS) {
+            self.pending_space = self.pending_breaks == 0;
+        }
+    }
+}
+
+/// Converts HTML markup into plain text.
+///
+/// Tags are removed, character references are decoded, and whitespace is
+/// collapsed. Paragraphs are separated by a blank line, and block elements
+/// and `br` elements start a new line. List items start with a bullet (`•`)
+/// or their number in ordered lists, and nested lists are indented. The
+/// whitespace in `pre` elements is kept.
+///
+/// ```rust
+/// use readfeed::text;
+///
+/// assert_eq!(
+///     "Lorem ipsum\n\n• One\n• Two\n\nDolor &",
+///     text::html_to_text("<p>Lorem <b>ipsum</b></p><ul><li>One<li>Two</ul>Dolor &amp;")
+/// );
+/// ```
+#[must_use]
+pub fn html_to_text(html: &str) -> String {
+    let mut text = PlainText {
+        output: String::with_capacity(html.len()),
+        ..PlainText::default()
+    };
+    let mut decoded = String::new();
+    // The hidden element and the depth of nested elements with the same name
+    let mut hidden: Option<(&str, usize)> = None;
+
+    tokenize_html(html, |token| match token {
+        HtmlToken::Markup(token::Ty::StartTag(tag)) => {
+            let name = tag.name().local().as_str();
+            if let Some((hidden_name, depth)) = &mut hidden {
+                if hidden_name.eq_ignore_ascii_case(name) {
+                    *depth += 1;
+                }
+            } else if let Some(hidden_name) = HIDDEN_ELEMENTS
+                .iter()
+                .find(|hidden_name| hidden_name.eq_ignore_ascii_case(name))
+            {
+                hidden = Some((hidden_name, 1));
+            } else {
+                text.start_element(name, tag.attributes());
+            }
+        }
+        HtmlToken::Markup(token::Ty::EmptyElementTag(tag)) => {
+            if hidden.is_none() {
+                text.empty_element(tag.name().local().as_str());
+            }
+        }
+        HtmlToken::Markup(token::Ty::EndTag(tag)) => {
+            let name = tag.name().local().as_str();
+            if let Some((hidden_name, depth)) = &mut hidden {
+                if hidden_name.eq_ignore_ascii_case(name) {
+                    *depth -= 1;
+                    if *depth == 0 {
+                        hidden = None;
+                    }
+                }
+            } else {
+                text.end_element(name);
+            }
+        }
+        HtmlToken::Markup(token::Ty::Characters(chars)) => {
+            if hidden.is_none() {
+                decoded.clear();
+                push_decoded(&mut decoded, chars.as_str());
+                text.push_text(&decoded);
+            }
+        }
+        HtmlToken::Markup(token::Ty::Cdata(cdata)) => {
+            if hidden.is_none() {
+                text.push_text(cdata.content().as_str());
+            }
+        }
+        HtmlToken::Text(chars) => {
+            if hidden.is_none() {
+                text.push_text(chars);
+            }
+        }
+        HtmlToken::Markup(
+            token::Ty::ProcessingInstruction(_) | token::Ty::Declaration(_) | token::Ty::Comment(_),
+        ) => {}
+    });
+
+    let mut output = text.output;
+    let len = output.trim_end().len();
+    output.truncate(len);
+    output
+}
+
+/// Escapes text so it can be used as HTML content or an attribute value.
+///
+/// ```rust
+/// use readfeed::text;
+///
+/// assert_eq!("&lt;b&gt; &amp; &quot;x&quot;", text::escape_html("<b> & \"x\""));
+/// ```
+#[must_use]
+pub fn escape_html(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            ch => output.push(ch),
+        }
+    }
+    output
+}
+
+/// Returns true if the character continues the preceding character (e.g. a
+/// combining accent, a variation selector, or an emoji skin tone).
+#[must_use]
+fn is_extending(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{300}'..='\u{36f}'
+            | '\u{1ab0}'..='\u{1aff}'
+            | '\u{1dc0}'..='\u{1dff}'
+            | '\u{200c}'..='\u{200d}'
+            | '\u{20d0}'..='\u{20ff}'
+            | '\u{fe00}'..='\u{fe0f}'
+            | '\u{fe20}'..='\u{fe2f}'
+            | '\u{1f3fb}'..='\u{1f3ff}'
+            | '\u{e0020}'..='\u{e007f}'
+            | '\u{e0100}'..='\u{e01ef}'
+    )
+}
+
+#[must_use]
+fn is_regional_indicator(ch: char) -> bool {
+    matches!(ch, '\u{1f1e6}'..='\u{1f1ff}')
+}
+
+/// Returns true if the text can be split at the byte index without splitting
+/// a user-perceived character.
+///
+/// Only common combining sequences are recognized.
+#[must_use]
+fn is_grapheme_boundary(text: &str, index: usize) -> bool {
+    let (before, after) = text.split_at(index);
+    let (Some(prev), Some(next)) = (before.chars().next_back(), after.chars().next()) else {
+        return true;
+    };
+    if is_extending(next) || prev == '\u{200d}' || (prev == '\r' && next == '\n') {
+        return false;
+    }
+    if is_regional_indicator(prev) && is_regional_indicator(next) {
+        // Flags are pairs of regional indicators
+        let count = before
+            .chars()
+            .rev()
+            .take_while(|ch| is_regional_indicator(*ch))
+            .count();
+        return count % 2 == 0;
+    }
+    true
+}
+
+/// Shortens text to at most `max_chars` characters.
+///
+/// If the text is longer, it is cut at the end of the last word which fits
+/// and an ellipsis (`…`) is appended. The ellipsis counts towards the
+/// maximum. If the last word which fits would leave less than half of the
+/// text, the text is cut between characters instead, but not within common
+/// combining sequences such as accented letters, emoji with modifiers, or
+/// flags. Whitespace and punctuation before the ellipsis are removed.
+///
+/// ```rust
+/// use readfeed::text;
+///
+/// assert_eq!("Lorem ipsum…", text::truncate("Lorem ipsum dolor sit amet", 15));
+/// assert_eq!("Lorem", text::truncate("Lorem", 5));
+/// assert_eq!("Loremipsu…", text::truncate("Loremipsumdolorsitamet", 10));
+/// ```
+#[must_use]
+pub fn truncate(text: &str, max_chars: usize) -> Cow<'_, str> {
+    let Some(keep) = max_chars.checked_sub(1) else {
+        return Cow::Borrowed("");
+    };
+    if text.chars().nth(max_chars).is_none() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut end = text.char_indices().nth(keep).map_or(text.len(), |(i, _)| i);
+    while end > 0 && !is_grapheme_boundary(text, end) {
+        end = text[..end].char_indices().next_back().map_or(0, |(i, _)| i);
+    }
+
+    let word_end = if text[end..].starts_with(char::is_whitespace) {
+        Some(end)
+    } else {
+        text[..end].rfind(char::is_whitespace)
+    };
+    if let Some(word_end) = word_end {
+        if text[..word_end].chars().count() * 2 >= keep {
+            end = word_end;
+        }
+    }
+
+    let mut output = String::from(text[..end].trim_end_matches(|ch: char| {
+        ch.is_whitespace() || matches!(ch, ',' | '.' | ':' | ';' | '-' | '\u{2013}' | '\u{2014}')
+    }));
+    output.push('\u{2026}');
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_html_to_text() {
+        assert_eq!(
+            "Lorem ipsum\n\ndolor \u{e9} & <sit>",
+            html_to_text(
+                "<p>Lorem <b>ipsum</b></p><p>dolor &eacute; &amp; &lt;sit&gt;</p>\n\
+                 <script>alert('x')</script><style>p { }</style>"
+            )
+        );
+        assert_eq!(
+            "One\nTwo\n\nThree",
+            html_to_text("One<br>Two<br/><br>Three")
+        );
+        assert_eq!("&bogus; \u{2026}", html_to_text("&bogus; &#x2026;"));
+        assert_eq!(
+            "Intro\n\n3. One\n  \u{2022} A\n  \u{2022} B\n4. Two\n\nfn main() {\n    x();\n}\n\nA B",
+            html_to_text(
+                "Intro<ol start=3><li>One<ul><li>A</li><li>B</li></ul></li><li>Two</li></ol>\
+                 <pre>\nfn main() {\n    x();\n}</pre><table><tr><td>A</td><td>B</td></tr></table>"
+            )
+        );
+        assert_eq!(
+            "Watch 1 < 2",
+            html_to_text("<video controls>Watch</video> 1 < 2")
+        );
+        assert_eq!(
+            "&lt;b&gt; &amp; &quot;x&quot; &#39;y&#39;",
+            escape_html("<b> & \"x\" 'y'")
+        );
+        assert_eq!(
+            Some((
+                String::from("<video controls=\"\" width=\"560\" title=\"a &quot;b&quot;\"/>"),
+                42
+            )),
+            normalize_tag("<video controls width=560 title='a \"b\"' />x")
+        );
+        assert_eq!(None, normalize_tag("< b"));
+    }
+
+    #[test]
+    fn truncate_text() {
+        assert_eq!("", truncate("Lorem", 0));
+        assert_eq!("Lorem ipsum", truncate("Lorem ipsum", 11));
+        assert_eq!("Lorem\u{2026}", truncate("Lorem, ipsum", 11));
+        assert_eq!("Lorem ipsum\u{2026}", truncate("Lorem ipsum dolor", 12));
+        assert_eq!("Lorem ipsum\u{2026}", truncate("Lorem ipsum dolor", 13));
+        assert_eq!("Lorem\u{2026}", truncate("Lorem ipsumdolorsit", 8));
+        assert_eq!("Lo ipsu\u{2026}", truncate("Lo ipsumdolorsit", 8));
+        assert_eq!("Cafe\u{301}\u{2026}", truncate("Cafe\u{301}s!", 6));
+        assert_eq!("Caf\u{2026}", truncate("Cafe\u{301}s!", 5));
+        assert_eq!(
+            "\u{1f1eb}\u{1f1f7}\u{2026}",
+            truncate("\u{1f1eb}\u{1f1f7}\u{1f1e9}\u{1f1ea}", 3)
+        );
+        assert_eq!("a\u{2026}", truncate("a\u{1f469}\u{200d}\u{1f4bb} b", 3));
+    }
+}
@@ -0,0 +1,388 @@
+//! Resolves relative references against the base URI of an element.
+//!
+//! The base URI of an element is set by the `xml:base` attributes of the
+//! element and its ancestors, relative to the URL the document was retrieved
+//! from. References are resolved following [RFC 3986][rfc3986] section 5.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::atom::{self, Elem, EntryElem, FeedElem};
+//!
+//! let input = r#"
+//! <feed xmlns="http://www.w3.org/2005/Atom" xml:base="/blog/">
+//!     <entry xml:base="2024/">
+//!         <link href="first-post.html"/>
+//!     </entry>
+//! </feed>
+//! "#;
+//!
+//! let mut iter = atom::Iter::new(input);
+//!
+//! let Some(Elem::Feed(mut feed_iter)) = iter.next() else {
+//!     panic!();
+//! };
+//! let Some(FeedElem::Entry(mut entry_iter)) = feed_iter.next() else {
+//!     panic!();
+//! };
+//!
+//! if let Some(EntryElem::Link(link)) = entry_iter.next() {
+//!     assert_eq!(vec!["/blog/", "2024/"], link.base().iter().collect::<Vec<_>>());
+//!     # #[cfg(feature = "alloc")]
+//!     assert_eq!(
+//!         Some("https://example.com/blog/2024/first-post.html"),
+//!         link.resolve_href("https://example.com/feed.xml").as_deref()
+//!     );
+//! } else {
+//!     panic!();
+//! }
+//! ```
+//!
+//! [rfc3986]: https://www.rfc-editor.org/rfc/rfc3986#section-5
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "alloc")]
+use crate::xml;
+
+/// The maximum number of nested relative `xml:base` values which are kept.
+const MAX_DEPTH: usize = 8;
+
+/// The `xml:base` values which apply to an element.
+///
+/// The values are kept from the outermost element to the innermost element.
+/// An absolute value replaces all of the outer values since they no longer
+/// affect the base URI. At most 8 nested relative values are kept; deeper
+/// values are ignored and [`Base::is_truncated()`] returns true.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base<'a> {
+    values: [&'a str; MAX_DEPTH],
+    len: usize,
+    is_truncated: bool,
+}
+
+impl<'a> Base<'a> {
+    #[must_use]
+    pub(crate) fn push(mut self, value: &'a str) -> Self {
+        let value = value.trim();
+        if has_scheme(value) {
+            self.len = 0;
+            self.is_truncated = false;
+        }
+        if self.len < MAX_DEPTH {
+            self.values[self.len] = value;
+            self.len += 1;
+        } else {
+            self.is_truncated = true;
+        }
+        self
+    }
+
+    /// Returns true if no `xml:base` values apply.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if nested relative `xml:base` values were ignored.
+    ///
+    /// The base URI does not include the ignored values and may be wrong.
+    #[inline]
+    #[must_use]
+    pub const fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+
+    /// The `xml:base` values from the outermost element to the innermost
+    /// element.
+    ///
+    /// The values are returned as written in the document without decoding
+    /// character references.
+    pub fn iter(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.values[..self.len].iter().copied()
+    }
+
+    /// Resolves the base URI against the document URL.
+    ///
+    /// Character references in the `xml:base` values are decoded.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn to_uri(&self, document_url: &str) -> String {
+        self.iter()
+            .fold(String::from(document_url.trim()), |base, value| {
+                resolve(&base, &xml::decode(value))
+            })
+    }
+
+    /// Resolves a reference against the base URI and the document URL.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn resolve(&self, document_url: &str, reference: &str) -> String {
+        resolve(&self.to_uri(document_url), reference)
+    }
+}
+
+#[must_use]
+fn has_scheme(value: &str) -> bool {
+    scheme(value).is_some()
+}
+
+#[must_use]
+pub(crate) fn scheme(value: &str) -> Option<&str> {
+    let (scheme, _) = value.split_once(':')?;
+    let mut bytes = scheme.bytes();
+    let is_scheme = bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'));
+    is_scheme.then_some(scheme)
+}
+
+/// The components of a URI reference.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Components<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Components<'a> {
+    fn parse(value: &'a str) -> Self {
+        let (rest, fragment) = match value.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (value, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let scheme = scheme(rest);
+        let rest = scheme.map_or(rest, |scheme| &rest[scheme.len() + 1..]);
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+        Self {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Removes `.` and `..` segments from a path.
+#[cfg(feature = "alloc")]
+#[must_use]
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+    while !input.is_empty() {
+        if let Some(rest) = input
+            .strip_prefix("../")
+            .or_else(|| input.strip_prefix("./"))
+        {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input.len() == 3 { "/" } else { &input[3..] };
+            let end = output.rfind('/').unwrap_or(0);
+            output.truncate(end);
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..].find('/').map_or(input.len(), |i| i + start);
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
+
+/// Merges a relative path with the path of the base URI.
+#[cfg(feature = "alloc")]
+#[must_use]
+fn merge(base: &Components<'_>, path: &str) -> String {
+    let mut merged = String::new();
+    if base.authority.is_some() && base.path.is_empty() {
+        merged.push('/');
+    } else if let Some(end) = base.path.rfind('/') {
+        merged.push_str(&base.path[..=end]);
+    }
+    merged.push_str(path);
+    merged
+}
+
+/// Resolves a reference against a base URI.
+///
+/// The reference and base are trimmed of surrounding whitespace. If the base
+/// is not an absolute URI, the result may still be a relative reference.
+///
+/// ```rust
+/// use readfeed::uri;
+///
+/// assert_eq!("http://a/b/c/g", uri::resolve("http://a/b/c/d;p?q", "g"));
+/// assert_eq!("http://a/b/g", uri::resolve("http://a/b/c/d;p?q", "../g"));
+/// assert_eq!("http://a/b/c/d;p?y", uri::resolve("http://a/b/c/d;p?q", "?y"));
+/// assert_eq!("https://example.com/", uri::resolve("http://a/b", "https://example.com/"));
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use]
+pub fn resolve(base: &str, reference: &str) -> String {
+    let base = Components::parse(base.trim());
+    let reference = Components::parse(reference.trim());
+
+    let (scheme, authority, path, query);
+    if reference.scheme.is_some() {
+        scheme = reference.scheme;
+        authority = reference.authority;
+        path = remove_dot_segments(reference.path);
+        query = reference.query;
+    } else {
+        scheme = base.scheme;
+        if reference.authority.is_some() {
+            authority = reference.authority;
+            path = remove_dot_segments(reference.path);
+            query = reference.query;
+        } else {
+            authority = base.authority;
+            if reference.path.is_empty() {
+                path = String::from(base.path);
+                query = reference.query.or(base.query);
+            } else {
+                path = if reference.path.starts_with('/') {
+                    remove_dot_segments(reference.path)
+                } else {
+                    remove_dot_segments(&merge(&base, reference.path))
+                };
+                query = reference.query;
+            }
+        }
+    }
+
+    let mut output = String::new();
+    if let Some(scheme) = scheme {
+        output.push_str(scheme);
+        output.push(':');
+    }
+    if let Some(authority) = authority {
+        output.push_str("//");
+        output.push_str(authority);
+    }
+    output.push_str(&path);
+    if let Some(query) = query {
+        output.push('?');
+        output.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        output.push('#');
+        output.push_str(fragment);
+    }
+    output
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_rfc3986_examples() {
+        let base = "http://a/b/c/d;p?q";
+        for (reference, expected) in [
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("g;x", "http://a/b/c/g;x"),
+            ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("../../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"),
+            ("g?y/../x", "http://a/b/c/g?y/../x"),
+            ("g#s/./x", "http://a/b/c/g#s/./x"),
+            ("g#s/../x", "http://a/b/c/g#s/../x"),
+            ("http:g", "http:g"),
+        ] {
+            assert_eq!(expected, resolve(base, reference), "{reference}");
+        }
+
+        assert_eq!("https://example.com/a", resolve("https://example.com", "a"));
+    }
+
+    #[test]
+    fn base_stack() {
+        let base = Base::default().push("https://example.com/feed/").push("a/");
+        assert_eq!(
+            "https://example.com/feed/a/",
+            base.to_uri("https://ignored/")
+        );
+
+        let base = base.push(" https://other.example/x/ ");
+        assert_eq!(1, base.iter().count());
+
+        let base = Base::default().push("https://example.com/?a=1&amp;b=2");
+        assert_eq!(
+            "https://example.com/post?c=3",
+            base.resolve("https://ignored/", "post?c=3")
+        );
+        assert_eq!(
+            "https://example.com/?a=1&b=2#top",
+            base.resolve("https://ignored/", "#top")
+        );
+
+        let base = (0..MAX_DEPTH).fold(Base::default(), |base, _| base.push("a/"));
+        assert!(!base.is_truncated());
+        let base = base.push("b/");
+        assert!(base.is_truncated());
+        assert_eq!(MAX_DEPTH, base.iter().count());
+        assert!(!base.push("https://example.com/").is_truncated());
+
+        let base = Base::default().push("/blog/").push("2024/");
+        assert_eq!(
+            "https://example.com/blog/2024/post",
+            base.resolve("https://example.com/feed.xml", "post")
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Typed values parsed from element content and attribute values.
+//!
+//! Parsing is lenient in a few defined ways:
+//!
+//! * Leading and trailing whitespace is ignored.
+//! * Numbers may have a leading `+` sign.
+//! * Booleans are `true` or `false` in any ASCII case (e.g. `True`).
+//! * Days of the week are English names in any ASCII case (e.g. `monday`).
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The reason a value could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorKind {
+    /// The value is empty or only whitespace.
+    Empty,
+    /// The value contains a character which is not a digit.
+    InvalidDigit,
+    /// The number is too large or too small for the type.
+    OutOfRange,
+    /// The value is not `true` or `false`.
+    InvalidBool,
+    /// The value is not the name of a day of the week.
+    InvalidWeekday,
+    /// The value is not valid base64.
+    InvalidBase64,
+}
+
+/// An error from parsing a typed value.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParseError {
+    kind: ErrorKind,
+}
+
+impl ParseError {
+    #[inline]
+    #[must_use]
+    const fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Empty => f.write_str("value is empty"),
+            ErrorKind::InvalidDigit => f.write_str("invalid digit found in number"),
+            ErrorKind::OutOfRange => f.write_str("number is out of range"),
+            ErrorKind::InvalidBool => f.write_str("expected `true` or `false`"),
+            ErrorKind::InvalidWeekday => f.write_str("expected the name of a day of the week"),
+            ErrorKind::InvalidBase64 => f.write_str("invalid base64 data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// The English name of the day.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+
+    /// The number of days since Monday.
+    #[must_use]
+    pub const fn days_from_monday(self) -> u8 {
+        self as u8
+    }
+}
+
+impl core::str::FromStr for Weekday {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(ParseError::new(ErrorKind::Empty));
+        }
+        Self::ALL
+            .into_iter()
+            .find(|day| day.name().eq_ignore_ascii_case(value))
+            .ok_or(ParseError::new(ErrorKind::InvalidWeekday))
+    }
+}
+
+pub(crate) fn parse_u64(value: &str) -> Result<u64, ParseError> {
+    let value = value.trim();
+    let digits = value.strip_prefix('+').unwrap_or(value);
+    if digits.is_empty() {
+        return Err(ParseError::new(ErrorKind::Empty));
+    }
+
+    digits.bytes().try_fold(0u64, |acc, b| {
+        if !b.is_ascii_digit() {
+            return Err(ParseError::new(ErrorKind::InvalidDigit));
+        }
+        acc.checked_mul(10)
+            .and_then(|acc| acc.checked_add(u64::from(b - b'0')))
+            .ok_or(ParseError::new(ErrorKind::OutOfRange))
+    })
+}
+
+pub(crate) fn parse_u32(value: &str) -> Result<u32, ParseError> {
+    parse_u64(value)
+        .and_then(|v| u32::try_from(v).map_err(|_| ParseError::new(ErrorKind::OutOfRange)))
+}
+
+pub(crate) fn parse_i32(value: &str) -> Result<i32, ParseError> {
+    let value = value.trim();
+    let (is_negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    if is_negative && digits.starts_with('+') {
+        return Err(ParseError::new(ErrorKind::InvalidDigit));
+    }
+    let magnitude =
+        i64::try_from(parse_u64(digits)?).map_err(|_| ParseError::new(ErrorKind::OutOfRange))?;
+    let value = if is_negative { -magnitude } else { magnitude };
+    i32::try_from(value).map_err(|_| ParseError::new(ErrorKind::OutOfRange))
+}
+
+pub(crate) fn parse_in_range(value: &str, max: u32) -> Result<u32, ParseError> {
+    parse_u32(value).and_then(|v| {
+        if v <= max {
+            Ok(v)
+        } else {
+            Err(ParseError::new(ErrorKind::OutOfRange))
+        }
+    })
+}
+
+pub(crate) fn parse_bool(value: &str) -> Result<bool, ParseError> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(ParseError::new(ErrorKind::Empty))
+    } else if value.eq_ignore_ascii_case("true") {
+        Ok(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Ok(false)
+    } else {
+        Err(ParseError::new(ErrorKind::InvalidBool))
+    }
+}
+
+/// Decodes base64 data.
+///
+/// Whitespace is ignored and padding is optional.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_base64(value: &str) -> Result<Vec<u8>, ParseError> {
+    let mut output = Vec::with_capacity(value.len() / 4 * 3);
+    let mut buf = 0u32;
+    let mut bits = 0;
+    let mut padding = 0;
+    for b in value.bytes().filter(|b| !b.is_ascii_whitespace()) {
+        let digit = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                padding += 1;
+                continue;
+            }
+            _ => return Err(ParseError::new(ErrorKind::InvalidBase64)),
+        };
+        if padding > 0 {
+            return Err(ParseError::new(ErrorKind::InvalidBase64));
+        }
+        buf = (buf << 6) | u32::from(digit);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buf >> bits) as u8);
+            buf &= (1 << bits) - 1;
+        }
+    }
+    // A single leftover digit cannot encode a byte
+    if bits >= 6 || padding > 2 {
+        return Err(ParseError::new(ErrorKind::InvalidBase64));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_numbers() {
+        assert_eq!(Ok(1024), parse_u64(" 1024\n"));
+        assert_eq!(Ok(7), parse_u64("+7"));
+        assert_eq!(Ok(u64::MAX), parse_u64("18446744073709551615"));
+        assert_eq!(
+            Err(ErrorKind::OutOfRange),
+            parse_u64("18446744073709551616").map_err(|e| e.kind())
+        );
+        assert_eq!(Err(ErrorKind::Empty), parse_u64("  ").map_err(|e| e.kind()));
+        assert_eq!(
+            Err(ErrorKind::InvalidDigit),
+            parse_u64("12px").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(ErrorKind::InvalidDigit),
+            parse_u64("-1").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(ErrorKind::OutOfRange),
+            parse_u32("4294967296").map_err(|e| e.kind())
+        );
+        assert_eq!(Ok(-20), parse_i32(" -20 "));
+        assert_eq!(Ok(i32::MIN), parse_i32("-2147483648"));
+        assert_eq!(
+            Err(ErrorKind::OutOfRange),
+            parse_i32("2147483648").map_err(|e| e.kind())
+        );
+        assert_eq!(
+            Err(ErrorKind::InvalidDigit),
+            parse_i32("-+1").map_err(|e| e.kind())
+        );
+        assert_eq!(Ok(23), parse_in_range("23", 23));
+        assert_eq!(
+            Err(ErrorKind::OutOfRange),
+            parse_in_range("24", 23).map_err(|e| e.kind())
+        );
+    }
+
+    #[test]
+    fn parse_bools_and_weekdays() {
+        assert_eq!(Ok(true), parse_bool("True"));
+        assert_eq!(Ok(false), parse_bool(" FALSE "));
+        assert_eq!(
+            Err(ErrorKind::InvalidBool),
+            parse_bool("yes").map_err(|e| e.kind())
+        );
+        assert_eq!(Ok(Weekday::Saturday), " saturday ".parse());
+        assert_eq!(
+            Err(ErrorKind::InvalidWeekday),
+            "Sat".parse::<Weekday>().map_err(|e| e.kind())
+        );
+        assert_eq!(6, Weekday::Sunday.days_from_monday());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn parse_base64() {
+        assert_eq!(
+            Ok(b"Lorem ipsum".to_vec()),
+            decode_base64("TG9yZW0g\naXBzdW0=")
+        );
+        assert_eq!(
+            Ok(b"Lorem ipsum".to_vec()),
+            decode_base64("TG9yZW0gaXBzdW0")
+        );
+        assert_eq!(Ok(b"".to_vec()), decode_base64(""));
+        for input in ["TG9yZW0*", "TQ=Q", "T", "TQ==="] {
+            assert_eq!(
+                Err(ErrorKind::InvalidBase64),
+                decode_base64(input).map_err(|e| e.kind()),
+                "{input}"
+            );
+        }
+    }
+}
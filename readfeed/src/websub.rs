@@ -0,0 +1,213 @@
+//! Discovers the [WebSub][websub] hubs and topic URL advertised by a feed.
+//!
+//! Atom feeds, RSS channels with `atom:link` elements, and [JSON Feed][json_feed]
+//! documents are supported.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use readfeed::websub;
+//!
+//! let input = r#"
+//! <rss xmlns:atom="http://www.w3.org/2005/Atom">
+//!     <channel>
+//!         <link>https://example.com/</link>
+//!         <atom:link rel="hub" href="https://hub.example.com/"/>
+//!         <atom:link rel="self" href="https://example.com/feed.xml" type="application/rss+xml"/>
+//!     </channel>
+//! </rss>
+//! "#;
+//!
+//! let discovery = websub::discover(input);
+//! assert_eq!(vec!["https://hub.example.com/"], discovery.hubs().collect::<Vec<_>>());
+//! assert_eq!(Some("https://example.com/feed.xml"), discovery.topic());
+//! ```
+//!
+//! [websub]: https://www.w3.org/TR/websub/
+//! [json_feed]: https://www.jsonfeed.org/version/1.1/
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{atom, json, rss, Ty};
+
+/// The hubs and topic URL of a feed.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Discovery<'a> {
+    hubs: Vec<Cow<'a, str>>,
+    topic: Option<Cow<'a, str>>,
+}
+
+impl<'a> Discovery<'a> {
+    /// The hub URLs in document order.
+    pub fn hubs(&self) -> impl Iterator<Item = &str> {
+        self.hubs.iter().map(AsRef::as_ref)
+    }
+
+    /// The canonical topic URL from the `self` link or the JSON Feed
+    /// `feed_url`.
+    #[must_use]
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+
+    fn add_hub(&mut self, href: Cow<'a, str>) {
+        if !self.hubs.contains(&href) {
+            self.hubs.push(href);
+        }
+    }
+
+    fn add_link(&mut self, rel: &str, href: &'a str) {
+        let href = href.trim();
+        for rel in rel.split_ascii_whitespace() {
+            if rel.eq_ignore_ascii_case("hub") {
+                self.add_hub(Cow::Borrowed(href));
+            } else if rel.eq_ignore_ascii_case("self") && self.topic.is_none() {
+                self.topic = Some(Cow::Borrowed(href));
+            }
+        }
+    }
+
+    /// Discovers the hubs and topic of an Atom feed.
+    #[must_use]
+    pub fn from_feed(feed: atom::FeedIter<'a>) -> Self {
+        let mut discovery = Self::default();
+        for elem in feed {
+            if let atom::FeedElem::Link(link) = elem {
+                if let (Some(rel), Some(href)) = (link.rel(), link.href()) {
+                    discovery.add_link(rel.as_str(), href.as_str());
+                }
+            }
+        }
+        discovery
+    }
+
+    /// Discovers the hubs and topic from the `atom:link` elements of an RSS
+    /// channel.
+    #[must_use]
+    pub fn from_channel(channel: rss::ChannelIter<'a>) -> Self {
+        let mut discovery = Self::default();
+        for elem in channel {
+            if let rss::ChannelElem::AtomLink(link) = elem {
+                if let (Some(rel), Some(href)) = (link.rel(), link.href()) {
+                    discovery.add_link(rel.as_str(), href.as_str());
+                }
+            }
+        }
+        discovery
+    }
+
+    /// Discovers the hubs and topic of a JSON Feed document.
+    #[must_use]
+    pub fn from_json(input: &'a str) -> Self {
+        let mut discovery = Self::default();
+        let Some(members) = json::members(input) else {
+            return discovery;
+        };
+        for (name, value) in members {
+            if name == "feed_url" {
+                discovery.topic = json::string(value);
+            } else if name == "hubs" {
+                let Some(hubs) = json::elements(value) else {
+                    continue;
+                };
+                for hub in hubs {
+                    let url = json::members(hub).and_then(|mut members| {
+                        members.find_map(|(name, value)| {
+                            (name == "url").then(|| json::string(value)).flatten()
+                        })
+                    });
+                    if let Some(url) = url {
+                        discovery.add_hub(url);
+                    }
+                }
+            }
+        }
+        discovery
+    }
+}
+
+/// Discovers the hubs and topic of a feed document.
+///
+/// The type of document is detected with [`detect_type()`][crate::detect_type].
+/// An empty [`Discovery`] is returned for other types of documents.
+#[must_use]
+pub fn discover(input: &str) -> Discovery<'_> {
+    match crate::detect_type(input) {
+        Ty::Atom => atom::Iter::new(input)
+            .find_map(|elem| match elem {
+                atom::Elem::Feed(feed) => Some(Discovery::from_feed(feed)),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        Ty::Rss => rss::Iter::new(input)
+            .find_map(|elem| match elem {
+                rss::Elem::Rss(mut rss) => rss.find_map(|elem| match elem {
+                    rss::RssElem::Channel(channel) => Some(Discovery::from_channel(channel)),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        Ty::Json => Discovery::from_json(input),
+        Ty::Unknown | Ty::XmlOrHtml => Discovery::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn discover_atom() {
+        let input = r#"
+<feed xmlns="http://www.w3.org/2005/Atom">
+    <link rel="alternate" href="https://example.com/"/>
+    <link rel="hub" href="https://hub1.example.com/"/>
+    <link rel="self" href="https://example.com/atom.xml"/>
+    <link rel="hub" href="https://hub2.example.com/"/>
+    <entry>
+        <link rel="self" href="https://example.com/1"/>
+    </entry>
+</feed>
+        "#;
+
+        let discovery = discover(input);
+        assert_eq!(
+            vec!["https://hub1.example.com/", "https://hub2.example.com/"],
+            discovery.hubs().collect::<Vec<_>>()
+        );
+        assert_eq!(Some("https://example.com/atom.xml"), discovery.topic());
+    }
+
+    #[test]
+    fn discover_json() {
+        let input = r#"
+{
+    "version": "https://jsonfeed.org/version/1.1",
+    "title": "Lorem ipsum",
+    "feed_url": "https:\/\/example.com\/feed.json",
+    "hubs": [
+        { "type": "WebSub", "url": "https://hub.example.com/" },
+        { "type": "WebSub", "url": "https:\/\/hub.example.com\/" }
+    ],
+    "items": []
+}
+        "#;
+
+        let discovery = discover(input);
+        assert_eq!(
+            vec!["https://hub.example.com/"],
+            discovery.hubs().collect::<Vec<_>>()
+        );
+        assert_eq!(Some("https://example.com/feed.json"), discovery.topic());
+    }
+
+    #[test]
+    fn discover_unknown() {
+        let discovery = discover("<html></html>");
+        assert_eq!(0, discovery.hubs().count());
+        assert_eq!(None, discovery.topic());
+    }
+}
@@ -1,9 +1,15 @@
 //! Provides types to represent elements in an [XML][xml] document.
 //!
 //! [xml]: https://www.w3.org/TR/2006/REC-xml11-20060816/
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+#[cfg(feature = "alloc")]
+use core::fmt;
+
 use maybe_xml::{token::prop::TagName, Reader};
 
-use crate::Ty;
+use crate::{lang::Language, uri::Base, Tag, Ty};
 
 fn map_tag_name_to_ty(tag_name: TagName<'_>) -> Ty {
     let local_name = tag_name.local().as_str();
@@ -20,19 +26,17 @@ pub(super) fn find_ty(input: &str) -> Ty {
     Reader::from_str(input)
         .into_iter()
         .find_map(|token| match token.ty() {
-            maybe_xml::token::Ty::StartTag(start_tag) => Some(map_tag_name_to_ty(start_tag.name())),
-            maybe_xml::token::Ty::EmptyElementTag(empty_tag) => {
-                Some(map_tag_name_to_ty(empty_tag.name()))
-            }
-            maybe_xml::token::Ty::EndTag(_) => Some(Ty::XmlOrHtml),
-            maybe_xml::token::Ty::Characters(chars) => {
+            token::Ty::StartTag(start_tag) => Some(map_tag_name_to_ty(start_tag.name())),
+            token::Ty::EmptyElementTag(empty_tag) => Some(map_tag_name_to_ty(empty_tag.name())),
+            token::Ty::EndTag(_) => Some(Ty::XmlOrHtml),
+            token::Ty::Characters(chars) => {
                 if chars.as_str().chars().all(|c| c.is_ascii_whitespace()) {
                     return None;
                 }
 
                 Some(Ty::XmlOrHtml)
             }
-            maybe_xml::token::Ty::Cdata(cdata) => {
+            token::Ty::Cdata(cdata) => {
                 if cdata
                     .content()
                     .as_str()
@@ -44,9 +48,9 @@ pub(super) fn find_ty(input: &str) -> Ty {
 
                 Some(Ty::XmlOrHtml)
             }
-            maybe_xml::token::Ty::ProcessingInstruction(_)
-            | maybe_xml::token::Ty::Declaration(_)
-            | maybe_xml::token::Ty::Comment(_) => None,
+            token::Ty::ProcessingInstruction(_)
+            | token::Ty::Declaration(_)
+            | token::Ty::Comment(_) => None,
         })
         .unwrap_or(Ty::Unknown)
 }
@@ -103,4 +107,349 @@ pub(crate) fn collect_bytes_until_end_tag<'a>(
     &input[begin..end]
 }
 
+/// The values of the `xml:` attributes inherited by an element.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Scope<'a> {
+    base: Base<'a>,
+    lang: Option<&'a str>,
+    channel_language: Option<&'a str>,
+    content_language: Option<&'a str>,
+}
+
+impl<'a> Scope<'a> {
+    /// The scope of an element with the given tag inside of this scope.
+    #[must_use]
+    pub(crate) fn enter(mut self, tag: Tag<'a>) -> Self {
+        if let Some(base) = tag.find_attribute("xml:base") {
+            self.base = self.base.push(base.as_str());
+        }
+        if let Some(lang) = tag.find_attribute("xml:lang") {
+            self.lang = Some(lang.as_str());
+        }
+        self
+    }
+
+    /// Sets the language from the `language` element of the enclosing RSS
+    /// channel.
+    ///
+    /// The channel's content is searched once when the channel iterator is
+    /// created. The search stops at the first `language` element.
+    #[must_use]
+    pub(crate) fn with_channel(mut self, content: &'a str) -> Self {
+        self.channel_language = find_channel_language(content);
+        self
+    }
+
+    #[must_use]
+    pub(crate) const fn with_content_language(mut self, language: &'a str) -> Self {
+        self.content_language = Some(language);
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn base(&self) -> Base<'a> {
+        self.base
+    }
+
+    /// The language from the nearest `xml:lang` attribute, the channel's
+    /// `language` element, or the `Content-Language`.
+    ///
+    /// An empty `xml:lang` attribute means the language is unknown.
+    #[must_use]
+    pub(crate) fn lang(&self) -> Option<Language<'a>> {
+        match self.lang {
+            Some(lang) => Language::new(lang),
+            None => self
+                .channel_language
+                .and_then(Language::new)
+                .or_else(|| self.content_language.and_then(Language::new)),
+        }
+    }
+}
+
+/// Finds the content of the first unprefixed `language` child element of an
+/// RSS channel.
+fn find_channel_language(channel: &str) -> Option<&str> {
+    let reader = Reader::from_str(channel);
+    let mut pos = 0;
+    while let Some(token) = reader.tokenize(&mut pos) {
+        if let token::Ty::StartTag(tag) = token.ty() {
+            let tag_name = tag.name();
+            let content = collect_bytes_until_end_tag(tag_name, &reader, &mut pos);
+            if tag_name.namespace_prefix().is_none()
+                && tag_name.local().as_str().eq_ignore_ascii_case("language")
+            {
+                return Some(content);
+            }
+        }
+    }
+    None
+}
+
+/// Replaces the predefined entity references and character references.
+///
+/// Unknown entity references are left as is.
+#[cfg(feature = "alloc")]
+fn unescape_into(input: &str, output: &mut String) {
+    let mut rest = input;
+    while let Some(index) = rest.find('&') {
+        output.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let name = &rest[1..end];
+        let ch = match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "apos" => Some('\''),
+            "quot" => Some('"'),
+            _ => name.strip_prefix('#').and_then(|num| {
+                if let Some(hex) = num.strip_prefix('x').or_else(|| num.strip_prefix('X')) {
+                    u32::from_str_radix(hex, 16).ok()
+                } else {
+                    num.parse::<u32>().ok()
+                }
+                .and_then(char::from_u32)
+            }),
+        };
+
+        if let Some(ch) = ch {
+            output.push(ch);
+            rest = &rest[end + 1..];
+        } else {
+            output.push('&');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+}
+
+/// Decodes the character data of an element's content.
+///
+/// Entity and character references are replaced, and `CDATA` sections are
+/// unwrapped. Any markup in the content is kept as is.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub(crate) fn decode(input: &str) -> Cow<'_, str> {
+    if !input.contains('&') && !input.contains("<![CDATA[") {
+        return Cow::Borrowed(input);
+    }
+
+    let reader = Reader::from_str(input);
+    let mut pos = 0;
+    let mut output = String::with_capacity(input.len());
+    while let Some(token) = reader.tokenize(&mut pos) {
+        match token.ty() {
+            token::Ty::Characters(chars) => {
+                unescape_into(chars.content().as_str(), &mut output);
+            }
+            token::Ty::Cdata(cdata) => {
+                output.push_str(cdata.content().as_str());
+            }
+            token::Ty::StartTag(_)
+            | token::Ty::EmptyElementTag(_)
+            | token::Ty::EndTag(_)
+            | token::Ty::ProcessingInstruction(_)
+            | token::Ty::Declaration(_)
+            | token::Ty::Comment(_) => {
+                output.push_str(token.as_str());
+            }
+        }
+    }
+    if pos < input.len() {
+        output.push_str(&input[pos..]);
+    }
+
+    Cow::Owned(output)
+}
+
+/// Removes the `div` element which wraps XHTML content and the namespace prefix
+/// of the XHTML elements.
+///
+/// If the content is not wrapped in a `div` element, the trimmed content is
+/// returned.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub(crate) fn unwrap_xhtml(content: &str) -> Cow<'_, str> {
+    let reader = Reader::from_str(content);
+    let mut pos = 0;
+    let (prefix, inner) = loop {
+        let Some(token) = reader.tokenize(&mut pos) else {
+            return Cow::Borrowed(content.trim());
+        };
+        match token.ty() {
+            token::Ty::StartTag(tag) if tag.name().local().as_str().eq_ignore_ascii_case("div") => {
+                let tag_name = tag.name();
+                let inner = collect_bytes_until_end_tag(tag_name, &reader, &mut pos);
+                break (tag_name.namespace_prefix(), inner);
+            }
+            token::Ty::EmptyElementTag(tag)
+                if tag.name().local().as_str().eq_ignore_ascii_case("div") =>
+            {
+                return Cow::Borrowed("");
+            }
+            token::Ty::Characters(chars) if chars.as_str().trim().is_empty() => {}
+            token::Ty::ProcessingInstruction(_) | token::Ty::Comment(_) => {}
+            _ => return Cow::Borrowed(content.trim()),
+        }
+    };
+
+    let Some(prefix) = prefix else {
+        return Cow::Borrowed(inner);
+    };
+    let prefix = prefix.as_str();
+
+    let reader = Reader::from_str(inner);
+    let mut pos = 0;
+    let mut output = String::with_capacity(inner.len());
+    while let Some(token) = reader.tokenize(&mut pos) {
+        let raw = token.as_str();
+        let (open, tag_name) = match token.ty() {
+            token::Ty::StartTag(tag) => ("<", tag.name()),
+            token::Ty::EmptyElementTag(tag) => ("<", tag.name()),
+            token::Ty::EndTag(tag) => ("</", tag.name()),
+            _ => {
+                output.push_str(raw);
+                continue;
+            }
+        };
+        if tag_name
+            .namespace_prefix()
+            .is_some_and(|p| p.as_str() == prefix)
+        {
+            output.push_str(open);
+            output.push_str(tag_name.local().as_str());
+            output.push_str(&raw[open.len() + tag_name.as_str().len()..]);
+        } else {
+            output.push_str(raw);
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Writes an XML document.
+///
+/// Element and attribute names are written as is. Text and attribute values
+/// are escaped, and characters which are not allowed in XML are removed.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub(crate) struct Writer<'w, W> {
+    w: &'w mut W,
+}
+
+#[cfg(feature = "alloc")]
+impl<'w, W: fmt::Write> Writer<'w, W> {
+    /// Starts a document with the XML declaration.
+    pub(crate) fn new(w: &'w mut W) -> Result<Self, fmt::Error> {
+        w.write_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n")?;
+        Ok(Self { w })
+    }
+
+    fn escape(&mut self, text: &str) -> fmt::Result {
+        for c in text.chars() {
+            match c {
+                '&' => self.w.write_str("&amp;")?,
+                '<' => self.w.write_str("&lt;")?,
+                '>' => self.w.write_str("&gt;")?,
+                '"' => self.w.write_str("&quot;")?,
+                '\t' | '\n' | '\r' => self.w.write_char(c)?,
+                '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+                c => self.w.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn tag(&mut self, name: &str, attributes: &[(&str, &str)]) -> fmt::Result {
+        self.w.write_char('<')?;
+        self.w.write_str(name)?;
+        for (name, value) in attributes {
+            write!(self.w, " {name}=\"")?;
+            self.escape(value)?;
+            self.w.write_char('"')?;
+        }
+        Ok(())
+    }
+
+    /// Writes a start tag.
+    pub(crate) fn start(&mut self, name: &str, attributes: &[(&str, &str)]) -> fmt::Result {
+        self.tag(name, attributes)?;
+        self.w.write_char('>')
+    }
+
+    /// Writes an end tag.
+    pub(crate) fn end(&mut self, name: &str) -> fmt::Result {
+        write!(self.w, "</{name}>")
+    }
+
+    /// Writes an empty element tag.
+    pub(crate) fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) -> fmt::Result {
+        self.tag(name, attributes)?;
+        self.w.write_str("/>")
+    }
+
+    /// Writes an element which only contains text.
+    pub(crate) fn text_element(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+        text: &str,
+    ) -> fmt::Result {
+        self.start(name, attributes)?;
+        self.escape(text)?;
+        self.end(name)
+    }
+}
+
 pub use maybe_xml::token;
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_text() {
+        assert!(matches!(
+            decode("Lorem ipsum"),
+            Cow::Borrowed("Lorem ipsum")
+        ));
+        assert_eq!(
+            "<p>Lorem & \"ipsum\"</p>",
+            decode("&lt;p&gt;Lorem &amp; &quot;ipsum&#34;&lt;/p&gt;")
+        );
+        assert_eq!(
+            "<p>Lorem &amp; ipsum</p> \u{e9}",
+            decode("<![CDATA[<p>Lorem &amp; ipsum</p>]]> &#xE9;")
+        );
+        assert_eq!("&nbsp; &", decode("&nbsp; &amp;"));
+        assert_eq!("<b>Lorem</b> & ipsum", decode("<b>Lorem</b> &amp; ipsum"));
+    }
+
+    #[test]
+    fn unwrap_xhtml_div() {
+        assert!(matches!(
+            unwrap_xhtml(
+                r#" <div xmlns="http://www.w3.org/1999/xhtml"><p>Lorem <b>ipsum</b></p></div> "#
+            ),
+            Cow::Borrowed("<p>Lorem <b>ipsum</b></p>")
+        ));
+        assert_eq!(
+            r#"<p class="a">Lorem<br/> <svg:svg></svg:svg> &amp; ipsum</p>"#,
+            unwrap_xhtml(
+                r#"
+<xhtml:div xmlns:xhtml="http://www.w3.org/1999/xhtml"><xhtml:p class="a">Lorem<xhtml:br/> <svg:svg></svg:svg> &amp; ipsum</xhtml:p></xhtml:div>
+                "#
+            )
+        );
+        assert_eq!(
+            "<div>Nested</div>",
+            unwrap_xhtml("<div><div>Nested</div></div>")
+        );
+        assert_eq!("", unwrap_xhtml("<div/>"));
+        assert_eq!("<p>Not wrapped</p>", unwrap_xhtml(" <p>Not wrapped</p> "));
+    }
+}